@@ -0,0 +1,25 @@
+use std::fs;
+use std::path::Path;
+
+use egui_i18n::codegen::Catalog;
+
+use crate::types::CodegenOpts;
+
+pub fn generate_module(opts: CodegenOpts) -> color_eyre::Result<()> {
+  let language = opts.language.as_deref().unwrap_or("en_US");
+  let catalog = Catalog::read(&opts.source_path, language)?;
+  log::debug!("collected messages: {:?}", catalog.messages.keys().collect::<Vec<_>>());
+
+  let module = catalog.to_rust();
+  match &opts.output_path {
+    Some(output_path) => {
+      if let Some(parent) = Path::new(output_path).parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+      }
+      fs::write(output_path, module)?;
+      println!("write module to: {}", output_path);
+    },
+    None => print!("{}", module),
+  }
+  Ok(())
+}
@@ -19,7 +19,7 @@ impl Generator {
   fn generate(&mut self) -> color_eyre::Result<()> {
     let source_path = &self.opts.source_path;
 
-    let mut visitor = TranslationVisitor::new();
+    let mut visitor = TranslationVisitor::new(self.opts.domain.clone().unwrap_or_default());
     let mut extensions = self.opts.extensions.clone();
     if extensions.is_empty() {
       extensions.push("rs".to_string());
@@ -107,13 +107,15 @@ impl Generator {
         if stored_translations.contains(t) {
           continue;
         }
-        let key = t.replace('=', "\\=");
+        let key = egui_i18n::escape_key(t);
         if is_default {
-          contents.push(format!("{} = {}", key, t));
+          // A key with a context is pre-filled without it.
+          let text = egui_i18n::split_context_key(t).map_or(t.as_str(), |(_, text)| text);
+          contents.push(format!("{} = {}", key, egui_i18n::escape_value(text)));
         } else {
           contents.push(format!("{} =", key));
         }
-        stored_translations.push(key);
+        stored_translations.push(t.clone());
       }
       if contents.is_empty() {
         continue;
@@ -129,12 +131,14 @@ impl Generator {
 }
 
 struct TranslationVisitor {
+  /// The domain whose keys are collected, empty for the core domain.
+  domain: String,
   translations: Vec<String>,
 }
 
 impl TranslationVisitor {
-  fn new() -> Self {
-    TranslationVisitor { translations: vec![] }
+  fn new(domain: String) -> Self {
+    TranslationVisitor { domain, translations: vec![] }
   }
 
   fn record_translation(&mut self, key: &str) {
@@ -161,8 +165,15 @@ impl TranslationVisitor {
 impl<'ast> Visit<'ast> for TranslationVisitor {
   fn visit_macro(&mut self, mac: &'ast syn::Macro) {
     if self.is_from_egui_i18n(&mac.path.segments) {
-      if let Some(first_arg) = extract_first_string_literal(&mac.tokens) {
-        self.record_translation(&first_arg);
+      let (domain, tokens) = split_option(mac.tokens.clone().into_iter().collect(), "domain", '=');
+      let (context, tokens) = split_option(tokens, "ctx", ':');
+      if domain.unwrap_or_default() == self.domain {
+        if let Some(first_arg) = extract_first_string_literal(tokens) {
+          match context {
+            Some(context) => self.record_translation(&egui_i18n::context_key(&context, &first_arg)),
+            None => self.record_translation(&first_arg),
+          }
+        }
       }
     }
 
@@ -170,8 +181,27 @@ impl<'ast> Visit<'ast> for TranslationVisitor {
   }
 }
 
-fn extract_first_string_literal(tokens: &proc_macro2::TokenStream) -> Option<String> {
-  let iter = tokens.clone().into_iter();
+/// Split a leading `name = "…",` (or `name: "…",` for `punct` `:`) off the
+/// macro arguments, e.g. `domain = "plugin-x",` or `ctx: "menu",`.
+fn split_option(
+  tokens: Vec<proc_macro2::TokenTree>,
+  name: &str,
+  punct: char,
+) -> (Option<String>, Vec<proc_macro2::TokenTree>) {
+  use proc_macro2::TokenTree::{Ident, Literal, Punct};
+
+  if let [Ident(ident), Punct(p), Literal(literal), Punct(comma), rest @ ..] = tokens.as_slice() {
+    if ident == name && p.as_char() == punct && comma.as_char() == ',' {
+      if let Ok(syn::Lit::Str(lit_str)) = syn::parse_str::<syn::Lit>(&literal.to_string()) {
+        return (Some(lit_str.value()), rest.to_vec());
+      }
+    }
+  }
+  (None, tokens)
+}
+
+fn extract_first_string_literal(tokens: Vec<proc_macro2::TokenTree>) -> Option<String> {
+  let iter = tokens.into_iter();
 
   for token in iter {
     if let proc_macro2::TokenTree::Literal(literal) = token {
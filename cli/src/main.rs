@@ -8,6 +8,7 @@ fn main() -> color_eyre::Result<()> {
   let opt = types::Opt::from_args();
   match opt.cmd {
     types::Command::Generate { opts } => command::generate::generate_languages(opts)?,
+    types::Command::Codegen { opts } => command::codegen::generate_module(opts)?,
   };
   Ok(())
 }
@@ -14,6 +14,11 @@ pub enum Command {
     #[structopt(flatten)]
     opts: GenerateOpts,
   },
+  /// Generate a Rust module with one function per message
+  Codegen {
+    #[structopt(flatten)]
+    opts: CodegenOpts,
+  },
 }
 
 #[derive(Clone, Debug, StructOpt)]
@@ -36,6 +41,23 @@ pub struct GenerateOpts {
   /// Language extension to use, default is `tgl`
   #[structopt(long)]
   pub ext: Option<LanguageExt>,
+  /// Only collect keys of `tr!(domain = "…", …)` calls in this domain,
+  /// default only keys without a domain
+  #[structopt(long)]
+  pub domain: Option<String>,
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub struct CodegenOpts {
+  /// Directory containing the `.egl` / `.ftl` catalogs
+  #[structopt(long)]
+  pub source_path: String,
+  /// File to write the module to, default stdout
+  #[structopt(long)]
+  pub output_path: Option<String>,
+  /// Language whose messages are generated, default `en_US`
+  #[structopt(long)]
+  pub language: Option<String>,
 }
 
 arg_enum! {
@@ -25,15 +25,16 @@ fn main() -> eframe::Result {
 }
 
 fn init() {
-  let en_us = String::from_utf8_lossy(include_bytes!("../../../assets/languages/classic/en_US.egl"));
-  let zh_cn = String::from_utf8_lossy(include_bytes!("../../../assets/languages/classic/zh_CN.egl"));
-  let ja_jp = String::from_utf8_lossy(include_bytes!("../../../assets/languages/classic/ja_JP.egl"));
-  egui_i18n::load_translations_from_text("en_US", en_us).unwrap();
-  egui_i18n::load_translations_from_text("zh_CN", zh_cn).unwrap();
-  egui_i18n::load_translations_from_text("ja_JP", ja_jp).unwrap();
+  // Every `.egl` file in the directory, embedded at build time. Files are
+  // routed by extension, so they load as classic even when another crate in
+  // the build enables the `fluent` feature.
+  static TRANSLATIONS: egui_i18n::Embedded =
+    egui_i18n_macros::include_translations!("../../assets/languages/classic");
+  egui_i18n::load_embedded(&TRANSLATIONS).unwrap();
 
-  egui_i18n::set_language("en_US");
-  egui_i18n::set_fallback("en_US");
+  // Pick the best loaded language from the user's environment, falling back
+  // to en_US.
+  egui_i18n::negotiate_language(&egui_i18n::EnvLanguages::new(), "en_US");
 }
 
 struct MyApp {
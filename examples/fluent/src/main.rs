@@ -31,17 +31,13 @@ fn init() {
   #[cfg(target_os = "windows")]
   egui_i18n::set_use_isolating(false);
 
-  let en_us = String::from_utf8_lossy(include_bytes!("../../../assets/languages/fluent/en-US.ftl"));
-  let zh_cn =
-    String::from_utf8_lossy(include_bytes!("../../../assets/languages/fluent/zh-Hans.ftl"));
-  let ja_jp = String::from_utf8_lossy(include_bytes!("../../../assets/languages/fluent/ja-JP.ftl"));
-  egui_i18n::load_translations_from_text("en-US", en_us).unwrap();
-  egui_i18n::load_translations_from_text("zh-Hans", zh_cn).unwrap();
-  egui_i18n::load_translations_from_text("ja-JP", ja_jp).unwrap();
+  // Every `.ftl` file in the directory, checked and embedded at build time.
+  static TRANSLATIONS: egui_i18n::Embedded =
+    egui_i18n_macros::include_translations!("../../assets/languages/fluent");
+  egui_i18n::load_embedded(&TRANSLATIONS).unwrap();
 
-  // 设置初始语言
-  egui_i18n::set_language("en-US");
-  egui_i18n::set_fallback("en-US");
+  // 根据系统语言设置初始语言，默认 en-US
+  egui_i18n::negotiate_language(&egui_i18n::EnvLanguages::new(), "en-US");
 }
 
 struct MyApp {
@@ -0,0 +1,62 @@
+//! Classic template rendering.
+//!
+//! `cargo bench -p egui-i18n --bench format`. `plain` is mostly the lookup
+//! itself; `greeting/replace` is the string replacement the classic backend
+//! used before templates were parsed at load time, without the lookup, as a
+//! baseline for what `greeting` spends on top of `plain`. `greeting/cached`
+//! is a cache hit.
+//!
+//! To compare two revisions, run `-- --save-baseline before` on the first and
+//! `-- --baseline before` on the second.
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egui_i18n::{Args, Format, I18n};
+
+const EGL: &str = "
+plain = Welcome back
+greeting = Hello, {name}! You have {count} new messages in {folder}.
+files = {count, plural, one {# file} other {# files}} in {folder}
+";
+
+fn replace(template: &str, args: &HashMap<&str, String>) -> String {
+  let mut result = template.to_string();
+  for (key, value) in args {
+    result = result.replace(&format!("{{{}}}", key), value);
+  }
+  result
+}
+
+fn bench_format(c: &mut Criterion) {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text_with_format("en-US", EGL, Format::Classic).unwrap();
+  i18n.set_language("en-US");
+
+  let mut args = Args::new();
+  args.set("name", "Ada");
+  args.set("count", 42);
+  args.set("folder", "Inbox");
+
+  let mut strings = HashMap::new();
+  strings.insert("name", "Ada".to_string());
+  strings.insert("count", "42".to_string());
+  strings.insert("folder", "Inbox".to_string());
+  let greeting = "Hello, {name}! You have {count} new messages in {folder}.";
+
+  let mut group = c.benchmark_group("classic");
+  group.bench_function("plain", |b| b.iter(|| i18n.translate(black_box("plain"), &args)));
+  group.bench_function("greeting", |b| b.iter(|| i18n.translate(black_box("greeting"), &args)));
+  group.bench_function("greeting/replace", |b| {
+    b.iter(|| replace(black_box(greeting), black_box(&strings)))
+  });
+  group.bench_function("plural", |b| b.iter(|| i18n.translate(black_box("files"), &args)));
+
+  i18n.set_cache_enabled(true);
+  group.bench_function("greeting/cached", |b| {
+    b.iter(|| i18n.translate_shared(black_box("greeting"), &args))
+  });
+  group.finish();
+}
+
+criterion_group!(benches, bench_format);
+criterion_main!(benches);
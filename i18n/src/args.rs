@@ -0,0 +1,335 @@
+#[cfg(feature = "classic")]
+use std::borrow::Cow;
+#[cfg(feature = "classic")]
+use std::collections::HashMap;
+#[cfg(feature = "classic")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Named arguments for a lookup that may be served by either backend.
+///
+/// `tr!` builds one of these; each value is stored in the shape every
+/// compiled-in backend expects, so the same call works whether the key ends
+/// up in a classic or a Fluent catalog.
+#[derive(Debug, Default)]
+pub struct Args<'a> {
+  #[cfg(feature = "classic")]
+  classic: HashMap<&'a str, ArgValue>,
+  #[cfg(feature = "fluent")]
+  fluent: crate::fluent::FluentArgs<'a>,
+  #[cfg(not(any(feature = "classic", feature = "fluent")))]
+  backend: std::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> Args<'a> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  #[cfg(all(feature = "classic", feature = "fluent"))]
+  pub fn set(&mut self, name: &'a str, value: impl IntoArg<'a>) {
+    let value: ArgValue = value.into();
+    self.fluent.set(name, value.clone());
+    self.classic.insert(name, value);
+  }
+
+  #[cfg(all(feature = "classic", not(feature = "fluent")))]
+  pub fn set(&mut self, name: &'a str, value: impl IntoArg<'a>) {
+    self.classic.insert(name, value.into());
+  }
+
+  #[cfg(all(feature = "fluent", not(feature = "classic")))]
+  pub fn set(&mut self, name: &'a str, value: impl IntoArg<'a>) {
+    self.fluent.set(name, value);
+  }
+
+  #[cfg(feature = "classic")]
+  pub(crate) fn classic(&self) -> &HashMap<&'a str, ArgValue> {
+    &self.classic
+  }
+
+  #[cfg(feature = "fluent")]
+  pub(crate) fn fluent(&self) -> &crate::fluent::FluentArgs<'a> {
+    &self.fluent
+  }
+
+  /// The names and values, sorted by name, as the cache keys results by. The
+  /// classic map holds every value when it is compiled in.
+  #[cfg(feature = "classic")]
+  pub(crate) fn cache_key(&self) -> Option<CacheKey> {
+    let mut entries = vec![];
+    for (name, value) in &self.classic {
+      let value = match value {
+        ArgValue::String(v) => KeyValue::String(v.clone()),
+        ArgValue::Int(v) => KeyValue::Int(*v),
+        ArgValue::Float(v) => KeyValue::Float(v.to_bits()),
+        ArgValue::Date(v) => KeyValue::Date(*v),
+        #[cfg(feature = "fluent")]
+        ArgValue::Fluent(v) => fluent_key(v)?,
+      };
+      entries.push((name.to_string(), value));
+    }
+    Some(CacheKey::new(entries))
+  }
+
+  /// The names and values, sorted by name, as the cache keys results by.
+  /// `None` for custom Fluent types, which cannot be compared.
+  #[cfg(all(feature = "fluent", not(feature = "classic")))]
+  pub(crate) fn cache_key(&self) -> Option<CacheKey> {
+    let mut entries = vec![];
+    for (name, value) in self.fluent.iter() {
+      entries.push((name.to_string(), fluent_key(value)?));
+    }
+    Some(CacheKey::new(entries))
+  }
+
+  #[cfg(not(any(feature = "classic", feature = "fluent")))]
+  pub(crate) fn cache_key(&self) -> Option<CacheKey> {
+    None
+  }
+}
+
+/// A set of arguments as the cache compares them: every name and value, in
+/// name order.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey(Vec<(String, KeyValue)>);
+
+impl CacheKey {
+  fn new(mut entries: Vec<(String, KeyValue)>) -> Self {
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    Self(entries)
+  }
+}
+
+/// An argument value that can be hashed and compared; floats by their bits.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum KeyValue {
+  String(String),
+  #[cfg(feature = "classic")]
+  Int(i64),
+  #[cfg(feature = "classic")]
+  Float(u64),
+  #[cfg(feature = "classic")]
+  Date(i64),
+  #[cfg(feature = "fluent")]
+  Number(u64, fluent_bundle::types::FluentNumberOptions),
+  #[cfg(feature = "fluent")]
+  None,
+  #[cfg(feature = "fluent")]
+  Error,
+}
+
+/// A Fluent value as a key, `None` for custom types, which cannot be
+/// compared.
+#[cfg(feature = "fluent")]
+fn fluent_key(value: &crate::fluent::FluentValue<'_>) -> Option<KeyValue> {
+  use crate::fluent::FluentValue;
+
+  Some(match value {
+    FluentValue::String(v) => KeyValue::String(v.to_string()),
+    FluentValue::Number(v) => KeyValue::Number(v.value.to_bits(), v.options.clone()),
+    FluentValue::None => KeyValue::None,
+    FluentValue::Error => KeyValue::Error,
+    FluentValue::Custom(_) => return None,
+  })
+}
+
+/// A value that can be passed to `tr!`.
+///
+/// Classic catalogs take an [`ArgValue`], which Fluent catalogs can use too,
+/// and with the `fluent` feature a `FluentValue` converts into one as is.
+/// Fluent-only builds take anything that converts into a `FluentValue`.
+#[cfg(feature = "classic")]
+pub trait IntoArg<'a>: Into<ArgValue> {}
+
+#[cfg(feature = "classic")]
+impl<T: Into<ArgValue>> IntoArg<'_> for T {}
+
+/// A value that can be passed to `tr!`.
+#[cfg(all(feature = "fluent", not(feature = "classic")))]
+pub trait IntoArg<'a>: Into<crate::fluent::FluentValue<'a>> {}
+
+#[cfg(all(feature = "fluent", not(feature = "classic")))]
+impl<'a, T: Into<crate::fluent::FluentValue<'a>>> IntoArg<'a> for T {}
+
+// ---------------------------------------------------------------------------
+// ArgValue
+// ---------------------------------------------------------------------------
+
+/// A typed argument for a classic catalog.
+///
+/// Values keep their type until a placeholder formats them, so
+/// `{count:number}` or `{when:date:long}` can format them for the catalog's
+/// language. Types without a conversion can be passed as `.to_string()`.
+#[cfg(feature = "classic")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgValue {
+  String(String),
+  Int(i64),
+  Float(f64),
+  /// A Unix timestamp in seconds. Dates are formatted without time zone
+  /// conversion.
+  Date(i64),
+  /// A Fluent value, passed to Fluent catalogs unchanged, so number options
+  /// and custom types keep working. Classic catalogs see its string or
+  /// number.
+  #[cfg(feature = "fluent")]
+  Fluent(crate::fluent::FluentValue<'static>),
+}
+
+#[cfg(feature = "classic")]
+impl ArgValue {
+  /// The value as a number: integers and floats, and strings that parse as
+  /// one.
+  pub fn as_number(&self) -> Option<f64> {
+    match self {
+      Self::String(v) => v.trim().parse().ok(),
+      Self::Int(v) => Some(*v as f64),
+      Self::Float(v) => Some(*v),
+      Self::Date(_) => None,
+      #[cfg(feature = "fluent")]
+      Self::Fluent(v) => match v {
+        crate::fluent::FluentValue::Number(n) => Some(n.value),
+        crate::fluent::FluentValue::String(v) => v.trim().parse().ok(),
+        _ => None,
+      },
+    }
+  }
+
+  /// The value as a Unix timestamp: dates, integers and floats (as seconds),
+  /// and ISO 8601 `YYYY-MM-DD[THH:MM[:SS]]` strings.
+  pub fn as_timestamp(&self) -> Option<i64> {
+    match self {
+      Self::String(v) => crate::intl::parse_timestamp(v.trim()),
+      Self::Int(v) | Self::Date(v) => Some(*v),
+      Self::Float(v) => Some(v.floor() as i64),
+      #[cfg(feature = "fluent")]
+      Self::Fluent(v) => match v {
+        crate::fluent::FluentValue::Number(n) => Some(n.value.floor() as i64),
+        crate::fluent::FluentValue::String(v) => crate::intl::parse_timestamp(v.trim()),
+        _ => None,
+      },
+    }
+  }
+}
+
+/// Strings and numbers as written; dates as ISO 8601 (`2025-01-31T15:04:05Z`).
+#[cfg(feature = "classic")]
+impl std::fmt::Display for ArgValue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::String(v) => f.write_str(v),
+      Self::Int(v) => write!(f, "{}", v),
+      Self::Float(v) => write!(f, "{}", v),
+      Self::Date(v) => f.write_str(&crate::intl::iso_datetime(*v)),
+      #[cfg(feature = "fluent")]
+      Self::Fluent(v) => {
+        use crate::fluent::FluentValue;
+
+        match v {
+          FluentValue::String(v) => f.write_str(v),
+          FluentValue::Number(n) => f.write_str(&n.as_string()),
+          FluentValue::Custom(v) => {
+            let intls = intl_memoizer::concurrent::IntlLangMemoizer::new(Default::default());
+            f.write_str(&v.as_string_threadsafe(&intls))
+          },
+          FluentValue::None | FluentValue::Error => Ok(()),
+        }
+      },
+    }
+  }
+}
+
+#[cfg(feature = "classic")]
+macro_rules! from_int {
+  ($($ty:ty)+) => {$(
+    impl From<$ty> for ArgValue {
+      fn from(value: $ty) -> Self {
+        match i64::try_from(value) {
+          Ok(v) => Self::Int(v),
+          Err(_) => Self::Float(value as f64),
+        }
+      }
+    }
+
+    impl From<&$ty> for ArgValue {
+      fn from(value: &$ty) -> Self {
+        Self::from(*value)
+      }
+    }
+  )+};
+}
+
+#[cfg(feature = "classic")]
+from_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+#[cfg(feature = "classic")]
+macro_rules! from_float {
+  ($($ty:ty)+) => {$(
+    impl From<$ty> for ArgValue {
+      fn from(value: $ty) -> Self {
+        Self::Float(value as f64)
+      }
+    }
+
+    impl From<&$ty> for ArgValue {
+      fn from(value: &$ty) -> Self {
+        Self::Float(*value as f64)
+      }
+    }
+  )+};
+}
+
+#[cfg(feature = "classic")]
+from_float!(f32 f64);
+
+#[cfg(feature = "classic")]
+macro_rules! from_text {
+  ($($ty:ty)+) => {$(
+    impl From<$ty> for ArgValue {
+      fn from(value: $ty) -> Self {
+        Self::String(value.to_string())
+      }
+    }
+  )+};
+}
+
+#[cfg(feature = "classic")]
+from_text!(String &String &str Cow<'_, str> char bool);
+
+/// The seconds since the Unix epoch, negative before it.
+#[cfg(feature = "classic")]
+impl From<SystemTime> for ArgValue {
+  fn from(value: SystemTime) -> Self {
+    match value.duration_since(UNIX_EPOCH) {
+      Ok(v) => Self::Date(v.as_secs() as i64),
+      Err(e) => Self::Date(-(e.duration().as_secs_f64().ceil() as i64)),
+    }
+  }
+}
+
+/// Dates become their timestamp, which Fluent's `DATETIME()` accepts.
+#[cfg(all(feature = "classic", feature = "fluent"))]
+impl From<ArgValue> for crate::fluent::FluentValue<'_> {
+  fn from(value: ArgValue) -> Self {
+    match value {
+      ArgValue::String(v) => v.into(),
+      ArgValue::Int(v) | ArgValue::Date(v) => v.into(),
+      ArgValue::Float(v) => v.into(),
+      ArgValue::Fluent(v) => v,
+    }
+  }
+}
+
+#[cfg(all(feature = "classic", feature = "fluent"))]
+impl From<crate::fluent::FluentValue<'_>> for ArgValue {
+  fn from(value: crate::fluent::FluentValue<'_>) -> Self {
+    Self::Fluent(value.into_owned())
+  }
+}
+
+#[cfg(all(feature = "classic", feature = "fluent"))]
+impl From<fluent_bundle::types::FluentNumber> for ArgValue {
+  fn from(value: fluent_bundle::types::FluentNumber) -> Self {
+    Self::Fluent(value.into())
+  }
+}
@@ -0,0 +1,122 @@
+//! Memoized results of [`I18n::translate`](crate::I18n::translate).
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::args::CacheKey;
+use crate::Args;
+
+/// Entries kept before the cache starts over, so arguments that change every
+/// frame (a timer, a counter) cannot grow it without bound.
+const MAX_ENTRIES: usize = 4096;
+
+/// Hit and miss counts of the translation cache, see
+/// [`I18n::cache_stats`](crate::I18n::cache_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+  /// Lookups served from the cache.
+  pub hits: u64,
+  /// Lookups that had to format the message.
+  pub misses: u64,
+  /// Results currently cached.
+  pub entries: usize,
+}
+
+/// Formatted messages by key and arguments.
+///
+/// Entries belong to the language chain and catalogs they were formatted
+/// with; [`invalidate`](Self::invalidate) drops them whenever either
+/// changes. The generation guards against a lookup that started before an
+/// invalidation storing its now stale result after it.
+#[derive(Default)]
+pub(crate) struct Cache {
+  enabled: AtomicBool,
+  hits: AtomicU64,
+  misses: AtomicU64,
+  entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+  generation: u64,
+  len: usize,
+  messages: HashMap<String, HashMap<CacheKey, Arc<str>>>,
+}
+
+impl Cache {
+  pub fn set_enabled(&self, enabled: bool) {
+    self.enabled.store(enabled, Ordering::Relaxed);
+    if !enabled {
+      self.invalidate();
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.enabled.load(Ordering::Relaxed)
+  }
+
+  /// What `args` are cached under, `None` when the cache is disabled or an
+  /// argument cannot be compared.
+  pub fn key(&self, args: &Args) -> Option<CacheKey> {
+    if !self.is_enabled() {
+      return None;
+    }
+    args.cache_key()
+  }
+
+  /// The cached result for `key` with arguments `args`, and the generation
+  /// to [`insert`](Self::insert) a freshly formatted one with.
+  pub fn get(&self, key: &str, args: &CacheKey) -> Result<Arc<str>, u64> {
+    let entries = self.entries.lock().unwrap();
+    match entries.messages.get(key).and_then(|messages| messages.get(args)) {
+      Some(value) => {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Ok(value.clone())
+      },
+      None => {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        Err(entries.generation)
+      },
+    }
+  }
+
+  /// Cache `value`, unless the cache was invalidated since `generation`.
+  pub fn insert(&self, generation: u64, key: &str, args: CacheKey, value: Arc<str>) {
+    let mut entries = self.entries.lock().unwrap();
+    if entries.generation != generation {
+      return;
+    }
+    if entries.len >= MAX_ENTRIES {
+      entries.messages.clear();
+      entries.len = 0;
+    }
+    let messages = entries.messages.entry(key.to_string()).or_default();
+    if messages.insert(args, value).is_none() {
+      entries.len += 1;
+    }
+  }
+
+  /// Drop every entry, keeping the counts.
+  pub fn invalidate(&self) {
+    let mut entries = self.entries.lock().unwrap();
+    entries.generation += 1;
+    entries.len = 0;
+    entries.messages.clear();
+  }
+
+  /// Drop every entry and reset the counts.
+  pub fn clear(&self) {
+    self.invalidate();
+    self.hits.store(0, Ordering::Relaxed);
+    self.misses.store(0, Ordering::Relaxed);
+  }
+
+  pub fn stats(&self) -> CacheStats {
+    CacheStats {
+      hits: self.hits.load(Ordering::Relaxed),
+      misses: self.misses.load(Ordering::Relaxed),
+      entries: self.entries.lock().unwrap().len,
+    }
+  }
+}
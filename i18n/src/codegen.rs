@@ -0,0 +1,380 @@
+//! Rust code generated from translation catalogs.
+//!
+//! [`Catalog::read`] collects the messages a language defines in `.egl` and
+//! `.ftl` files, with the placeholders each one takes, and
+//! [`Catalog::to_rust`] turns them into one function per message:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!   egui_i18n::codegen::build("i18n", "en-US", "msgs.rs").unwrap();
+//! }
+//!
+//! // src/main.rs
+//! #[allow(dead_code)]
+//! mod msgs {
+//!   include!(concat!(env!("OUT_DIR"), "/msgs.rs"));
+//! }
+//!
+//! ui.label(msgs::hello_name(&self.name));
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fluent_syntax::ast;
+
+use crate::{locale, Format, I18nError};
+
+/// A message of the reference language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+  /// The argument names the message uses, in order of first use.
+  pub placeholders: Vec<String>,
+  /// The message as written in the catalog.
+  pub text: String,
+}
+
+/// The messages one language defines across its catalog files.
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+  /// The files read, sorted.
+  pub files: Vec<PathBuf>,
+  /// Messages by key: classic keys, Fluent messages with a value,
+  /// `message.attribute` and `-term`.
+  pub messages: BTreeMap<String, Message>,
+}
+
+impl Catalog {
+  /// Read the `.egl` and `.ftl` files in `dir` whose stem is `language`,
+  /// compared in normalized form so `en_US.egl` matches `en-US`. Finding none
+  /// is an [`I18nError::Io`] error.
+  ///
+  /// As at runtime, a Fluent message wins over a classic one with the same
+  /// key. Fluent syntax errors are skipped; loading reports them.
+  pub fn read(dir: impl AsRef<Path>, language: &str) -> Result<Self, I18nError> {
+    let dir = dir.as_ref();
+    let files = language_files(dir, language)?;
+    if files.is_empty() {
+      let message = format!("no `{}.egl` or `{}.ftl` catalog", language, language);
+      let source = io::Error::new(io::ErrorKind::NotFound, message);
+      return Err(I18nError::Io { path: dir.to_path_buf(), source });
+    }
+
+    let mut messages = BTreeMap::new();
+    let mut fluent = vec![];
+    for file in &files {
+      let content = match fs::read_to_string(file) {
+        Ok(v) => v,
+        Err(source) => return Err(I18nError::Io { path: file.clone(), source }),
+      };
+      match format_of(file) {
+        Some(Format::Classic) => {
+          for (key, text) in crate::parse_translations(&content, false) {
+            let placeholders = crate::placeholder_names(&text);
+            messages.insert(key, Message { placeholders, text });
+          }
+        },
+        _ => fluent.push(content),
+      }
+    }
+    let resources: Vec<_> = fluent
+      .iter()
+      .map(|content| {
+        fluent_syntax::parser::parse_runtime(content.as_str())
+          .unwrap_or_else(|(resource, _)| resource)
+      })
+      .collect();
+    messages.extend(fluent_messages(&resources));
+    Ok(Self { files, messages })
+  }
+
+  /// A Rust module with one function per message, named after its key in
+  /// snake case (`hello-name` → `hello_name`, `login.tooltip` →
+  /// `login_tooltip`, `[menu] Open` → `menu_open`). Each takes the
+  /// message's placeholders as [`IntoArg`](crate::IntoArg) parameters and
+  /// translates through the default instance, like `tr!`. Terms are left
+  /// out.
+  ///
+  /// The module has no inner attributes or docs, so it can be used with
+  /// `include!`.
+  pub fn to_rust(&self) -> String {
+    let files: Vec<String> = self
+      .files
+      .iter()
+      .filter_map(|file| file.file_name())
+      .map(|name| name.to_string_lossy().to_string())
+      .collect();
+    let mut out = format!(
+      "// Generated by egui-i18n from {}. Do not edit.\n",
+      if files.is_empty() { "no files".to_string() } else { files.join(", ") }
+    );
+
+    let mut taken = HashSet::new();
+    for (key, message) in self.messages.iter().filter(|(key, _)| !key.starts_with('-')) {
+      let name = unique(identifier(key), &mut taken);
+      out.push('\n');
+      let shown = match crate::split_context_key(key) {
+        Some((context, key)) => format!("[{}] {}", context, key),
+        None => key.clone(),
+      };
+      let _ = writeln!(out, "/// `{}`", shown.replace('`', "'"));
+      out.push_str("///\n/// ```text\n");
+      for line in message.text.lines() {
+        let _ = writeln!(out, "/// {}", line.replace("```", "'''"));
+      }
+      out.push_str("/// ```\n");
+
+      // `args` is the local the arguments are collected in.
+      let mut params = HashSet::from(["args".to_string()]);
+      let params: Vec<(String, &str)> = message
+        .placeholders
+        .iter()
+        .map(|placeholder| (unique(identifier(placeholder), &mut params), placeholder.as_str()))
+        .collect();
+      if params.is_empty() {
+        let _ = writeln!(out, "pub fn {}() -> ::std::string::String {{", name);
+        let _ = writeln!(out, "    ::egui_i18n::translate({:?}, &::egui_i18n::Args::new())", key);
+        out.push_str("}\n");
+        continue;
+      }
+      let signature: Vec<String> = params
+        .iter()
+        .map(|(param, _)| format!("{}: impl ::egui_i18n::IntoArg<'a>", param))
+        .collect();
+      let signature = match signature.as_slice() {
+        [param] => param.clone(),
+        params => format!("\n    {},\n", params.join(",\n    ")),
+      };
+      let _ = writeln!(out, "pub fn {}<'a>({}) -> ::std::string::String {{", name, signature);
+      out.push_str("    let mut args = ::egui_i18n::Args::new();\n");
+      for (param, placeholder) in &params {
+        let _ = writeln!(out, "    args.set({:?}, {});", placeholder, param);
+      }
+      let _ = writeln!(out, "    ::egui_i18n::translate({:?}, &args)", key);
+      out.push_str("}\n");
+    }
+    out
+  }
+}
+
+/// For build scripts: write the module for `language`'s catalogs in `dir`
+/// to `$OUT_DIR/file_name` and have Cargo rerun the script when they change.
+/// Returns the written path.
+pub fn build(
+  dir: impl AsRef<Path>,
+  language: &str,
+  file_name: impl AsRef<Path>,
+) -> Result<PathBuf, I18nError> {
+  let dir = dir.as_ref();
+  println!("cargo:rerun-if-changed={}", dir.display());
+  let catalog = Catalog::read(dir, language)?;
+  for file in &catalog.files {
+    println!("cargo:rerun-if-changed={}", file.display());
+  }
+  let out_dir = std::env::var_os("OUT_DIR").map(PathBuf::from).unwrap_or_default();
+  let path = out_dir.join(file_name);
+  match fs::write(&path, catalog.to_rust()) {
+    Ok(()) => Ok(path),
+    Err(source) => Err(I18nError::Io { path, source }),
+  }
+}
+
+fn format_of(file: &Path) -> Option<Format> {
+  Format::from_extension(file.extension()?.to_string_lossy())
+}
+
+fn language_files(dir: &Path, language: &str) -> Result<Vec<PathBuf>, I18nError> {
+  let read_dir = match fs::read_dir(dir) {
+    Ok(v) => v,
+    Err(source) => return Err(I18nError::Io { path: dir.to_path_buf(), source }),
+  };
+  let language = locale::normalize(language);
+  let mut files: Vec<PathBuf> = read_dir
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| path.is_file() && format_of(path).is_some())
+    .filter(|path| {
+      let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+      locale::normalize(&stem) == language
+    })
+    .collect();
+  files.sort();
+  Ok(files)
+}
+
+// ---------------------------------------------------------------------------
+// Identifiers
+// ---------------------------------------------------------------------------
+
+const KEYWORDS: &[&str] = &[
+  "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do",
+  "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+  "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+  "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+  "use", "virtual", "where", "while", "yield",
+];
+
+/// `text` as a snake case identifier: ASCII letters and digits, everything
+/// else an underscore. Keywords become raw identifiers; text without a
+/// letter or digit is named after a hash of itself.
+fn identifier(text: &str) -> String {
+  let mut out = String::new();
+  let mut previous_lower = false;
+  for c in text.chars() {
+    if c.is_ascii_alphanumeric() {
+      // `userName` → `user_name`
+      if c.is_ascii_uppercase() && previous_lower {
+        out.push('_');
+      }
+      previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+      out.push(c.to_ascii_lowercase());
+    } else {
+      previous_lower = false;
+      if !out.is_empty() && !out.ends_with('_') {
+        out.push('_');
+      }
+    }
+  }
+  let out = out.trim_end_matches('_');
+  match out {
+    "" => format!("message_{:08x}", fnv1a(text)),
+    "self" | "Self" | "super" | "crate" => format!("{}_", out),
+    out if out.starts_with(|c: char| c.is_ascii_digit()) => format!("_{}", out),
+    out if KEYWORDS.contains(&out) => format!("r#{}", out),
+    out => out.to_string(),
+  }
+}
+
+/// `name`, or `name_2`, `name_3`, … when it is already taken.
+fn unique(name: String, taken: &mut HashSet<String>) -> String {
+  let mut candidate = name.clone();
+  let mut n = 2;
+  while !taken.insert(candidate.clone()) {
+    candidate = format!("{}_{}", name.trim_start_matches("r#"), n);
+    n += 1;
+  }
+  candidate
+}
+
+fn fnv1a(text: &str) -> u32 {
+  text.bytes().fold(0x811c_9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+/// Messages by key. A message's placeholders include those of the messages
+/// it references, which Fluent formats with the same arguments.
+fn fluent_messages(resources: &[ast::Resource<&str>]) -> HashMap<String, Message> {
+  let mut patterns: HashMap<String, &ast::Pattern<&str>> = HashMap::new();
+  for entry in resources.iter().flat_map(|resource| &resource.body) {
+    let (prefix, id, value, attributes) = match entry {
+      ast::Entry::Message(message) => {
+        ("", message.id.name, message.value.as_ref(), &message.attributes)
+      },
+      ast::Entry::Term(term) => ("-", term.id.name, Some(&term.value), &term.attributes),
+      _ => continue,
+    };
+    if let Some(value) = value {
+      patterns.insert(format!("{}{}", prefix, id), value);
+    }
+    for attribute in attributes {
+      patterns.insert(format!("{}{}.{}", prefix, id, attribute.id.name), &attribute.value);
+    }
+  }
+  patterns
+    .iter()
+    .map(|(key, pattern)| {
+      let mut variables = Variables { patterns: &patterns, seen: HashSet::new(), names: vec![] };
+      variables.message(key);
+      (key.clone(), Message { placeholders: variables.names, text: pattern_text(pattern) })
+    })
+    .collect()
+}
+
+/// The pattern as Fluent source, without the `key =` in front.
+fn pattern_text(pattern: &ast::Pattern<&str>) -> String {
+  let message = ast::Message {
+    id: ast::Identifier { name: "m" },
+    value: Some(pattern.clone()),
+    attributes: vec![],
+    comment: None,
+  };
+  let resource = ast::Resource { body: vec![ast::Entry::Message(message)] };
+  let source = fluent_syntax::serializer::serialize(&resource);
+  let source = source.strip_prefix("m =").unwrap_or(&source);
+  // Multi-line patterns start on the next line, indented.
+  let lines: Vec<&str> = source.trim_start_matches(' ').trim_start_matches('\n').lines().collect();
+  let lines: Vec<&str> =
+    lines.iter().map(|line| line.strip_prefix("    ").unwrap_or(line)).collect();
+  lines.join("\n")
+}
+
+/// Collects the variables of a message, walking the messages it references
+/// where they appear.
+struct Variables<'a, 'p> {
+  patterns: &'a HashMap<String, &'p ast::Pattern<&'p str>>,
+  seen: HashSet<&'a str>,
+  names: Vec<String>,
+}
+
+impl<'a> Variables<'a, '_> {
+  fn message(&mut self, key: &str) {
+    let Some((key, pattern)) = self.patterns.get_key_value(key) else {
+      return;
+    };
+    if self.seen.insert(key) {
+      self.pattern(pattern);
+    }
+  }
+
+  fn pattern(&mut self, pattern: &ast::Pattern<&str>) {
+    for element in &pattern.elements {
+      if let ast::PatternElement::Placeable { expression } = element {
+        self.expression(expression);
+      }
+    }
+  }
+
+  fn expression(&mut self, expression: &ast::Expression<&str>) {
+    match expression {
+      ast::Expression::Select { selector, variants } => {
+        self.inline(selector);
+        for variant in variants {
+          self.pattern(&variant.value);
+        }
+      },
+      ast::Expression::Inline(inline) => self.inline(inline),
+    }
+  }
+
+  /// Terms get their variables from their call arguments, so their own
+  /// variables are not the message's.
+  fn inline(&mut self, inline: &ast::InlineExpression<&str>) {
+    match inline {
+      ast::InlineExpression::VariableReference { id } => {
+        if !self.names.iter().any(|name| name == id.name) {
+          self.names.push(id.name.to_string());
+        }
+      },
+      ast::InlineExpression::FunctionReference { arguments, .. } => {
+        let named = arguments.named.iter().map(|argument| &argument.value);
+        for argument in arguments.positional.iter().chain(named) {
+          self.inline(argument);
+        }
+      },
+      ast::InlineExpression::MessageReference { id, attribute } => match attribute {
+        Some(attribute) => self.message(&format!("{}.{}", id.name, attribute.name)),
+        None => self.message(id.name),
+      },
+      ast::InlineExpression::Placeable { expression } => self.expression(expression),
+      ast::InlineExpression::StringLiteral { .. }
+      | ast::InlineExpression::NumberLiteral { .. }
+      | ast::InlineExpression::TermReference { .. } => {},
+    }
+  }
+}
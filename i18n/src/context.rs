@@ -0,0 +1,39 @@
+use egui::{Context, Id};
+
+use crate::I18n;
+
+fn storage_id() -> Id {
+  Id::new("egui-i18n")
+}
+
+/// Per-`egui::Context` translator storage.
+///
+/// Each context lazily gets its own empty [`I18n`] the first time
+/// [`i18n`](I18nContextExt::i18n) is called, so several egui apps (or test
+/// harnesses) running in one process keep separate languages, fallbacks and
+/// catalogs. Use [`set_i18n`](I18nContextExt::set_i18n) to share one
+/// translator between contexts.
+///
+/// ```ignore
+/// use egui_i18n::I18nContextExt;
+///
+/// ctx.i18n().load_translations_from_text("en-US", content)?;
+/// ctx.i18n().set_language("en-US");
+/// ```
+pub trait I18nContextExt {
+  /// The translator attached to this context, created on first access.
+  fn i18n(&self) -> I18n;
+
+  /// Attach `i18n` to this context, replacing any previous translator.
+  fn set_i18n(&self, i18n: I18n);
+}
+
+impl I18nContextExt for Context {
+  fn i18n(&self) -> I18n {
+    self.data_mut(|data| data.get_temp_mut_or_insert_with(storage_id(), I18n::new).clone())
+  }
+
+  fn set_i18n(&self, i18n: I18n) {
+    self.data_mut(|data| data.insert_temp(storage_id(), i18n));
+  }
+}
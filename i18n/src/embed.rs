@@ -0,0 +1,52 @@
+//! Translation files compiled into the binary.
+
+use crate::Format;
+
+/// One translation file embedded by `include_translations!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmbeddedFile {
+  /// The file's path as found at build time, relative to the crate that
+  /// embedded it. Fluent resources are identified by it.
+  pub path: &'static str,
+  /// The language the file is for: its stem, as with
+  /// [`load_translations_from_path`](crate::load_translations_from_path).
+  pub language: &'static str,
+  /// The format its extension names.
+  pub format: Format,
+  pub content: &'static str,
+}
+
+/// The translation files of a directory, embedded at build time by
+/// `egui_i18n_macros::include_translations!`, which checks them as it does.
+///
+/// Loading them needs no filesystem, so it works on wasm as well:
+///
+/// ```ignore
+/// static TRANSLATIONS: egui_i18n::Embedded =
+///   egui_i18n_macros::include_translations!("assets/languages");
+///
+/// egui_i18n::load_embedded(&TRANSLATIONS)?;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Embedded {
+  files: &'static [EmbeddedFile],
+}
+
+impl Embedded {
+  pub const fn new(files: &'static [EmbeddedFile]) -> Self {
+    Self { files }
+  }
+
+  /// The files, sorted by path.
+  pub fn files(&self) -> &'static [EmbeddedFile] {
+    self.files
+  }
+
+  /// The languages with at least one file, sorted and without duplicates.
+  pub fn languages(&self) -> Vec<&'static str> {
+    let mut languages: Vec<&'static str> = self.files.iter().map(|file| file.language).collect();
+    languages.sort_unstable();
+    languages.dedup();
+    languages
+  }
+}
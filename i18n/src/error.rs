@@ -0,0 +1,254 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::Format;
+
+/// Errors returned by the translation loaders.
+///
+/// Every variant keeps the underlying error as its
+/// [`source`](std::error::Error::source), and — where it is known — the
+/// language and the file the catalog came from, so tooling can point
+/// translators to the exact spot.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum I18nError {
+  /// Reading a translation file or directory failed.
+  Io { path: PathBuf, source: std::io::Error },
+  /// The language is not a valid BCP-47 identifier (Fluent bundles need one).
+  InvalidLanguage {
+    language: String,
+    path: Option<PathBuf>,
+    source: unic_langid::LanguageIdentifierError,
+  },
+  /// A catalog contains syntax errors.
+  Parse { language: String, path: Option<PathBuf>, errors: Vec<ParseError> },
+  /// A Fluent resource parsed, but could not be added to the bundle, e.g.
+  /// because it redefines a message.
+  #[cfg(feature = "fluent")]
+  Resource { language: String, path: Option<PathBuf>, errors: Vec<crate::fluent::FluentError> },
+  /// The backend for this format is not compiled in.
+  FormatNotEnabled { format: Format },
+}
+
+impl I18nError {
+  /// The language being loaded, when the error is tied to one.
+  pub fn language(&self) -> Option<&str> {
+    match self {
+      Self::InvalidLanguage { language, .. } => Some(language),
+      Self::Parse { language, .. } => Some(language),
+      #[cfg(feature = "fluent")]
+      Self::Resource { language, .. } => Some(language),
+      Self::Io { .. } | Self::FormatNotEnabled { .. } => None,
+    }
+  }
+
+  /// The file being loaded, when the error is tied to one.
+  pub fn path(&self) -> Option<&Path> {
+    match self {
+      Self::Io { path, .. } => Some(path),
+      Self::InvalidLanguage { path, .. } => path.as_deref(),
+      Self::Parse { path, .. } => path.as_deref(),
+      #[cfg(feature = "fluent")]
+      Self::Resource { path, .. } => path.as_deref(),
+      Self::FormatNotEnabled { .. } => None,
+    }
+  }
+
+  /// Attach the file the failing content was read from.
+  pub(crate) fn with_path(mut self, file: &Path) -> Self {
+    match &mut self {
+      Self::InvalidLanguage { path, .. } => *path = Some(file.to_path_buf()),
+      Self::Parse { path, .. } => *path = Some(file.to_path_buf()),
+      #[cfg(feature = "fluent")]
+      Self::Resource { path, .. } => *path = Some(file.to_path_buf()),
+      Self::Io { .. } | Self::FormatNotEnabled { .. } => {},
+    }
+    self
+  }
+}
+
+/// `language` or `language (path)`, for messages.
+struct Origin<'a>(&'a str, &'a Option<PathBuf>);
+
+impl fmt::Display for Origin<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.1 {
+      Some(path) => write!(f, "{:?} ({})", self.0, path.display()),
+      None => write!(f, "{:?}", self.0),
+    }
+  }
+}
+
+impl fmt::Display for I18nError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Io { path, source } => write!(f, "failed to read {}: {}", path.display(), source),
+      Self::InvalidLanguage { language, path, source } => {
+        write!(f, "invalid language identifier {}: {}", Origin(language, path), source)
+      },
+      Self::Parse { language, path, errors } => {
+        write!(f, "failed to parse {}", Origin(language, path))?;
+        if let Some(first) = errors.first() {
+          write!(f, " at {}", first)?;
+        }
+        if errors.len() > 1 {
+          write!(f, " (and {} more)", errors.len() - 1)?;
+        }
+        Ok(())
+      },
+      #[cfg(feature = "fluent")]
+      Self::Resource { language, path, errors } => {
+        write!(f, "failed to add resource to {}", Origin(language, path))?;
+        if let Some(first) = errors.first() {
+          write!(f, ": {}", first)?;
+        }
+        if errors.len() > 1 {
+          write!(f, " (and {} more)", errors.len() - 1)?;
+        }
+        Ok(())
+      },
+      Self::FormatNotEnabled { format } => {
+        let feature = match format {
+          Format::Classic => "classic",
+          Format::Fluent => "fluent",
+        };
+        write!(f, "the {} format is not enabled; enable the `{}` feature", feature, feature)
+      },
+    }
+  }
+}
+
+impl std::error::Error for I18nError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Io { source, .. } => Some(source),
+      Self::InvalidLanguage { source, .. } => Some(source),
+      Self::Parse { errors, .. } => errors.first().map(|e| &e.error as _),
+      #[cfg(feature = "fluent")]
+      Self::Resource { errors, .. } => errors.first().map(|e| e as _),
+      Self::FormatNotEnabled { .. } => None,
+    }
+  }
+}
+
+/// One syntax error in a catalog, with its 1-based position.
+#[derive(Debug)]
+pub struct ParseError {
+  pub line: usize,
+  pub column: usize,
+  pub error: SyntaxError,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "line {}, column {}: {}", self.line, self.column, self.error)
+  }
+}
+
+/// What is wrong at a [`ParseError`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SyntaxError {
+  /// Always a [`FluentError::ParserError`](crate::fluent::FluentError::ParserError).
+  #[cfg(feature = "fluent")]
+  Fluent(crate::fluent::FluentError),
+  /// A classic `{name, plural, …}` or `{name, select, …}` placeholder still
+  /// open at the next key or at the end of the file.
+  UnclosedSelector,
+}
+
+impl fmt::Display for SyntaxError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      #[cfg(feature = "fluent")]
+      Self::Fluent(error) => error.fmt(f),
+      Self::UnclosedSelector => f.write_str("unclosed plural or select placeholder"),
+    }
+  }
+}
+
+impl std::error::Error for SyntaxError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      #[cfg(feature = "fluent")]
+      Self::Fluent(error) => Some(error),
+      Self::UnclosedSelector => None,
+    }
+  }
+}
+
+/// The 1-based line and column (in characters) of byte `offset` in `content`.
+/// Also used by `egui-i18n-macros` to report embedded catalogs.
+pub fn line_column(content: &str, offset: usize) -> (usize, usize) {
+  let mut offset = offset.min(content.len());
+  while !content.is_char_boundary(offset) {
+    offset -= 1;
+  }
+  let before = &content[..offset];
+  let line = before.matches('\n').count() + 1;
+  let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+  let column = before[line_start..].chars().count() + 1;
+  (line, column)
+}
+
+/// The errors raised while formatting one Fluent message.
+#[cfg(feature = "fluent")]
+#[derive(Debug, Clone)]
+pub struct FormatErrors {
+  /// The catalog the message was formatted from.
+  pub language: String,
+  pub key: String,
+  pub errors: Vec<crate::fluent::FluentError>,
+}
+
+#[cfg(feature = "fluent")]
+impl fmt::Display for FormatErrors {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "failed to format {:?} in {:?}", self.key, self.language)?;
+    for (i, error) in self.errors.iter().enumerate() {
+      write!(f, "{} {}", if i == 0 { ":" } else { ";" }, error)?;
+    }
+    Ok(())
+  }
+}
+
+/// What [`I18n::translate`](crate::I18n::translate) — and so `tr!` — does
+/// when formatting a Fluent message raises errors. The formatted string, with
+/// Fluent's placeholders for the failing parts, is returned either way.
+///
+/// `Log` and `Collect` report each language and key once, until catalogs are
+/// reloaded; `PanicInDebug` and `Custom` see every occurrence.
+#[cfg(feature = "fluent")]
+#[derive(Clone, Default)]
+pub enum FormatErrorHook {
+  /// Discard the errors, as lookups did before.
+  Ignore,
+  /// Log them with `log::warn!`.
+  #[default]
+  Log,
+  /// Panic in debug builds, log in release builds.
+  PanicInDebug,
+  /// Keep them until [`I18n::take_format_errors`](crate::I18n::take_format_errors).
+  Collect,
+  Custom(std::sync::Arc<dyn Fn(&FormatErrors) + Send + Sync>),
+}
+
+#[cfg(feature = "fluent")]
+impl FormatErrorHook {
+  pub fn custom(hook: impl Fn(&FormatErrors) + Send + Sync + 'static) -> Self {
+    Self::Custom(std::sync::Arc::new(hook))
+  }
+}
+
+#[cfg(feature = "fluent")]
+impl fmt::Debug for FormatErrorHook {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Ignore => f.write_str("Ignore"),
+      Self::Log => f.write_str("Log"),
+      Self::PanicInDebug => f.write_str("PanicInDebug"),
+      Self::Collect => f.write_str("Collect"),
+      Self::Custom(_) => f.write_str("Custom(..)"),
+    }
+  }
+}
@@ -0,0 +1,51 @@
+/// The syntax of a translation file, which decides the backend it is loaded
+/// into.
+///
+/// Both backends can be compiled in at the same time; each language or file
+/// is then routed to the classic or the Fluent catalogs by its format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+  /// Key-value `.egl` / `.properties` files (`classic` feature).
+  Classic,
+  /// Mozilla Fluent `.ftl` files (`fluent` feature).
+  Fluent,
+}
+
+impl Format {
+  /// The format a file extension (without the dot, case-insensitive) maps to.
+  pub fn from_extension(ext: impl AsRef<str>) -> Option<Self> {
+    match ext.as_ref().to_lowercase().as_str() {
+      "egl" => Some(Self::Classic),
+      "ftl" => Some(Self::Fluent),
+      _ => None,
+    }
+  }
+
+  /// The canonical file extension for this format.
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Self::Classic => "egl",
+      Self::Fluent => "ftl",
+    }
+  }
+
+  /// Whether the backend for this format is compiled in.
+  pub fn is_enabled(&self) -> bool {
+    match self {
+      Self::Classic => cfg!(feature = "classic"),
+      Self::Fluent => cfg!(feature = "fluent"),
+    }
+  }
+}
+
+/// The format used when none is given, e.g. by `load_translations_from_text`:
+/// Fluent when the `fluent` feature is enabled, classic otherwise.
+impl Default for Format {
+  fn default() -> Self {
+    if cfg!(feature = "fluent") {
+      Self::Fluent
+    } else {
+      Self::Classic
+    }
+  }
+}
@@ -0,0 +1,1050 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+#[cfg(feature = "fluent")]
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "fluent")]
+use std::sync::Mutex;
+use std::sync::{Arc, RwLock};
+
+use crate::args::CacheKey;
+use crate::cache::Cache;
+#[cfg(feature = "fluent")]
+use crate::fluent::{FluentArgs, FluentError, FluentValue};
+use crate::source::subdomain;
+#[cfg(feature = "classic")]
+use crate::ArgValue;
+use crate::{
+  locale, vendor, Args, CacheStats, Embedded, FileSource, Format, I18nError, LanguageSource,
+  Layout, LoadMode, LoadReport, MemorySource, MissingKey, MissingKeyPolicy, SourceEntry,
+  TranslationSource,
+};
+#[cfg(feature = "fluent")]
+use crate::{FormatErrorHook, FormatErrors, Override};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+struct Config {
+  language: String,
+  fallbacks: Vec<String>,
+  /// [`locale::fallback_chain`] of `language` and `fallbacks`, recomputed
+  /// whenever either changes.
+  chain: Arc<Vec<String>>,
+  missing_key_policy: MissingKeyPolicy,
+  #[cfg(feature = "fluent")]
+  use_isolating: bool,
+  #[cfg(feature = "fluent")]
+  format_error_hook: FormatErrorHook,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      language: String::new(),
+      fallbacks: vec![],
+      chain: Arc::default(),
+      missing_key_policy: MissingKeyPolicy::default(),
+      #[cfg(feature = "fluent")]
+      use_isolating: true,
+      #[cfg(feature = "fluent")]
+      format_error_hook: FormatErrorHook::default(),
+    }
+  }
+}
+
+impl Config {
+  fn update_chain(&mut self) {
+    self.chain = Arc::new(locale::fallback_chain(&self.language, &self.fallbacks));
+  }
+}
+
+// ---------------------------------------------------------------------------
+// I18n
+// ---------------------------------------------------------------------------
+
+/// A translator that owns its language configuration and loaded catalogs.
+///
+/// `I18n` is a cheap handle: clones share the same state, and the catalogs are
+/// freed once the last clone is dropped. Independently created instances never
+/// see each other's languages or catalogs, so differently configured
+/// translators can live side by side.
+///
+/// The free functions at the crate root (`set_language`, `tr!`, …) operate on
+/// the process-wide [`I18n::global`] instance. With the `egui` feature enabled,
+/// every `egui::Context` can carry its own instance via
+/// [`I18nContextExt`](crate::I18nContextExt).
+#[derive(Clone, Default)]
+pub struct I18n {
+  inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+  config: RwLock<Config>,
+  cache: Cache,
+  /// The core domain, which `tr!` without a domain and every loader without
+  /// one use.
+  core: Arc<Catalogs>,
+  /// The named domains, see [`I18n::load_domain_from_source`].
+  domains: RwLock<HashMap<String, Arc<Catalogs>>>,
+  #[cfg(feature = "fluent")]
+  format_errors: Mutex<FormatErrorLog>,
+}
+
+/// The catalogs of one domain.
+#[derive(Default)]
+struct Catalogs {
+  #[cfg(feature = "classic")]
+  classic: vendor::classic::Translations,
+  #[cfg(feature = "fluent")]
+  fluent: vendor::fluent::Translations,
+  /// The loaded languages as `(normalized, name)`, see [`refresh`](Self::refresh).
+  index: RwLock<Vec<(String, String)>>,
+}
+
+impl Catalogs {
+  /// No catalogs, and the Fluent functions of `other`.
+  fn empty_like(other: &Catalogs) -> Self {
+    #[cfg(not(feature = "fluent"))]
+    let _ = other;
+    Self {
+      #[cfg(feature = "classic")]
+      classic: Default::default(),
+      #[cfg(feature = "fluent")]
+      fluent: other.fluent.empty_with_functions(),
+      index: Default::default(),
+    }
+  }
+
+  /// Re-index the loaded languages after a load or unload. Catalogs keep the
+  /// name they were loaded under (`en_US`, `en-US`, …); lookups match them
+  /// against the language chain by their normalized form.
+  fn refresh(&self) {
+    *self.index.write().unwrap() = self
+      .languages()
+      .into_iter()
+      .map(|language| (locale::normalize(&language), language))
+      .collect();
+  }
+
+  /// Every language loaded into any backend, without duplicates.
+  fn languages(&self) -> Vec<String> {
+    let mut languages: Vec<String> = vec![];
+    #[cfg(feature = "fluent")]
+    languages.extend(self.fluent.languages());
+    #[cfg(feature = "classic")]
+    for language in self.classic.languages() {
+      if !languages.contains(&language) {
+        languages.push(language);
+      }
+    }
+    languages
+  }
+}
+
+/// Formatting errors already reported, and those kept for
+/// [`I18n::take_format_errors`].
+#[cfg(feature = "fluent")]
+#[derive(Default)]
+struct FormatErrorLog {
+  reported: HashSet<(String, String)>,
+  collected: Vec<FormatErrors>,
+}
+
+impl std::fmt::Debug for I18n {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("I18n")
+      .field("language", &self.get_language())
+      .field("fallbacks", &self.get_fallbacks())
+      .field("languages", &self.languages())
+      .finish()
+  }
+}
+
+impl I18n {
+  /// Create an empty translator with no language configured and no catalogs
+  /// loaded.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The process-wide default instance behind the crate-level free functions
+  /// and `tr!`.
+  pub fn global() -> &'static I18n {
+    &crate::DEFAULT
+  }
+
+  /// Whether `self` and `other` are handles to the same translator.
+  pub fn ptr_eq(&self, other: &I18n) -> bool {
+    Arc::ptr_eq(&self.inner, &other.inner)
+  }
+
+  pub fn set_language(&self, locale: &str) {
+    let mut config = self.inner.config.write().unwrap();
+    config.language = locale.to_string();
+    config.update_chain();
+    self.inner.cache.invalidate();
+  }
+
+  pub fn get_language(&self) -> String {
+    self.inner.config.read().unwrap().language.clone()
+  }
+
+  /// Use `locale` as the only fallback; an empty string clears the fallbacks.
+  pub fn set_fallback(&self, locale: &str) {
+    let fallbacks = if locale.is_empty() { vec![] } else { vec![locale.to_string()] };
+    let mut config = self.inner.config.write().unwrap();
+    config.fallbacks = fallbacks;
+    config.update_chain();
+    self.inner.cache.invalidate();
+  }
+
+  /// The first fallback, or an empty string when none is set.
+  pub fn get_fallback(&self) -> String {
+    self.inner.config.read().unwrap().fallbacks.first().cloned().unwrap_or_default()
+  }
+
+  /// Replace the fallbacks with `locales`, tried in order after the active
+  /// language.
+  pub fn set_fallbacks(&self, locales: &[impl AsRef<str>]) {
+    let fallbacks = locales
+      .iter()
+      .map(|locale| locale.as_ref().to_string())
+      .filter(|locale| !locale.is_empty())
+      .collect();
+    let mut config = self.inner.config.write().unwrap();
+    config.fallbacks = fallbacks;
+    config.update_chain();
+    self.inner.cache.invalidate();
+  }
+
+  pub fn get_fallbacks(&self) -> Vec<String> {
+    self.inner.config.read().unwrap().fallbacks.clone()
+  }
+
+  /// The normalized tags a lookup tries, in order: the active language and
+  /// its less specific forms, then each fallback the same way. See
+  /// [`locale::fallback_chain`].
+  pub fn language_chain(&self) -> Vec<String> {
+    self.inner.config.read().unwrap().chain.to_vec()
+  }
+
+  /// Pick the language and fallbacks from the user's preferred languages.
+  ///
+  /// The languages reported by `source` are [negotiated](locale::negotiate)
+  /// against [`languages`](Self::languages). The best match becomes the active
+  /// language and the remaining matches, followed by `default`, become the
+  /// fallbacks. When nothing matches, `default` is used as the language.
+  /// Returns the selected language.
+  pub fn negotiate_language(&self, source: &impl LanguageSource, default: &str) -> String {
+    let mut matched = locale::negotiate(&source.preferred_languages(), &self.languages());
+    if !default.is_empty() && !matched.iter().any(|v| v == default) {
+      matched.push(default.to_string());
+    }
+    let language = if matched.is_empty() { String::new() } else { matched.remove(0) };
+
+    let mut config = self.inner.config.write().unwrap();
+    config.language = language.clone();
+    config.fallbacks = matched;
+    config.update_chain();
+    self.inner.cache.invalidate();
+    language
+  }
+
+  /// Choose what lookups return for keys that no catalog in the
+  /// [`language_chain`](Self::language_chain) has. Defaults to
+  /// [`MissingKeyPolicy::Empty`]. Missing keys are recorded either way, see
+  /// [`missing_keys`](crate::missing_keys).
+  pub fn set_missing_key_policy(&self, policy: MissingKeyPolicy) {
+    self.inner.config.write().unwrap().missing_key_policy = policy;
+  }
+
+  pub fn get_missing_key_policy(&self) -> MissingKeyPolicy {
+    self.inner.config.read().unwrap().missing_key_policy.clone()
+  }
+
+  #[cfg(feature = "fluent")]
+  pub fn set_use_isolating(&self, value: bool) {
+    self.inner.config.write().unwrap().use_isolating = value;
+    self.inner.cache.invalidate();
+  }
+
+  #[cfg(feature = "fluent")]
+  pub fn get_use_isolating(&self) -> bool {
+    self.inner.config.read().unwrap().use_isolating
+  }
+
+  /// Choose what [`translate`](Self::translate) does when formatting a Fluent
+  /// message raises errors. Defaults to [`FormatErrorHook::Log`].
+  #[cfg(feature = "fluent")]
+  pub fn set_format_error_hook(&self, hook: FormatErrorHook) {
+    self.inner.config.write().unwrap().format_error_hook = hook;
+  }
+
+  #[cfg(feature = "fluent")]
+  pub fn get_format_error_hook(&self) -> FormatErrorHook {
+    self.inner.config.read().unwrap().format_error_hook.clone()
+  }
+
+  /// Drain the errors gathered under [`FormatErrorHook::Collect`].
+  #[cfg(feature = "fluent")]
+  pub fn take_format_errors(&self) -> Vec<FormatErrors> {
+    std::mem::take(&mut self.inner.format_errors.lock().unwrap().collected)
+  }
+
+  /// Every language loaded into any backend of the core domain, without
+  /// duplicates.
+  pub fn languages(&self) -> Vec<String> {
+    self.inner.core.languages()
+  }
+
+  /// See [`load_translations_from_map`](crate::load_translations_from_map).
+  #[cfg(feature = "classic")]
+  pub fn load_translations_from_map(
+    &self,
+    language: impl AsRef<str>,
+    translations: HashMap<String, String>,
+  ) -> Result<(), I18nError> {
+    self.inner.core.classic.load_translations_from_map(language, "", translations);
+    self.changed(&self.inner.core);
+    Ok(())
+  }
+
+  #[cfg(not(feature = "classic"))]
+  pub fn load_translations_from_map(
+    &self,
+    _language: impl AsRef<str>,
+    _translations: HashMap<String, String>,
+  ) -> Result<(), I18nError> {
+    Err(I18nError::FormatNotEnabled { format: Format::Classic })
+  }
+
+  /// Load `content` in the default [`Format`]: Fluent when the `fluent`
+  /// feature is enabled, classic otherwise.
+  pub fn load_translations_from_text(
+    &self,
+    language: impl AsRef<str>,
+    content: impl AsRef<str>,
+  ) -> Result<(), I18nError> {
+    self.load_translations_from_text_with_format(language, content, Format::default())
+  }
+
+  /// Load `content` into the backend for `format`.
+  ///
+  /// The content goes into the language's unnamed resource, replacing only
+  /// earlier unnamed content and keeping files loaded for the language; see
+  /// [`add_resource`](Self::add_resource).
+  pub fn load_translations_from_text_with_format(
+    &self,
+    language: impl AsRef<str>,
+    content: impl AsRef<str>,
+    format: Format,
+  ) -> Result<(), I18nError> {
+    self.load_resource(&self.inner.core, language.as_ref(), "", content.as_ref(), format)
+  }
+
+  /// Add Fluent `content` to `language` as resource `id`, keeping the
+  /// language's other resources. A resource loaded earlier under the same id
+  /// is replaced in place.
+  ///
+  /// A message or term that another resource already defines is an
+  /// [`I18nError::Resource`] error, and nothing is loaded; use
+  /// [`add_resource_overriding`](Self::add_resource_overriding) to let the
+  /// new definitions win instead.
+  #[cfg(feature = "fluent")]
+  pub fn add_resource(
+    &self,
+    language: impl AsRef<str>,
+    id: impl AsRef<str>,
+    content: impl AsRef<str>,
+  ) -> Result<(), I18nError> {
+    let (language, id, content) = (language.as_ref(), id.as_ref(), content.as_ref());
+    self.add_fluent_resource(&self.inner.core, language, id, content, false)?;
+    Ok(())
+  }
+
+  /// Like [`add_resource`](Self::add_resource), but definitions in `content`
+  /// replace those of earlier resources. Returns what was replaced.
+  #[cfg(feature = "fluent")]
+  pub fn add_resource_overriding(
+    &self,
+    language: impl AsRef<str>,
+    id: impl AsRef<str>,
+    content: impl AsRef<str>,
+  ) -> Result<Vec<Override>, I18nError> {
+    let (language, id, content) = (language.as_ref(), id.as_ref(), content.as_ref());
+    self.add_fluent_resource(&self.inner.core, language, id, content, true)
+  }
+
+  /// Unload Fluent resource `id` from `language`. The language itself is
+  /// unloaded with its last resource. Returns whether the resource was loaded.
+  #[cfg(feature = "fluent")]
+  pub fn remove_resource(&self, language: impl AsRef<str>, id: impl AsRef<str>) -> bool {
+    let use_isolating = self.get_use_isolating();
+    let removed =
+      self.inner.core.fluent.remove_resource(language.as_ref(), id.as_ref(), use_isolating);
+    self.changed(&self.inner.core);
+    removed
+  }
+
+  /// The ids of the Fluent resources loaded for `language`, in load order.
+  /// Text loaded without an id has the empty id; files loaded from a path
+  /// use the file's path.
+  #[cfg(feature = "fluent")]
+  pub fn resources(&self, language: impl AsRef<str>) -> Vec<String> {
+    self.inner.core.fluent.resources(language.as_ref())
+  }
+
+  /// Make `function` callable as `NAME(...)` from the Fluent messages of every
+  /// loaded and future bundle. `NUMBER` and `DATETIME` are built in;
+  /// registering one of those names replaces the built-in.
+  ///
+  /// Fluent function names are upper case (`[A-Z][A-Z0-9_-]*`); others can
+  /// not be called from messages.
+  #[cfg(feature = "fluent")]
+  pub fn add_function<F>(&self, name: impl AsRef<str>, function: F)
+  where
+    F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync + 'static,
+  {
+    let use_isolating = self.get_use_isolating();
+    let function = Arc::new(function);
+    // Held throughout, so a domain created meanwhile cannot miss the function.
+    let domains = self.inner.domains.read().unwrap();
+    for catalogs in std::iter::once(&self.inner.core).chain(domains.values()) {
+      catalogs.fluent.add_function(name.as_ref(), function.clone(), use_isolating);
+    }
+    self.inner.cache.invalidate();
+  }
+
+  /// Call after loading into or unloading from `catalogs`.
+  fn changed(&self, catalogs: &Catalogs) {
+    catalogs.refresh();
+    self.inner.cache.invalidate();
+  }
+
+  fn load_resource(
+    &self,
+    catalogs: &Catalogs,
+    language: &str,
+    id: &str,
+    content: &str,
+    format: Format,
+  ) -> Result<(), I18nError> {
+    match format {
+      #[cfg(feature = "classic")]
+      Format::Classic => {
+        catalogs.classic.load_translations_from_text(language, id, content)?;
+        self.changed(catalogs);
+        Ok(())
+      },
+      #[cfg(feature = "fluent")]
+      Format::Fluent => {
+        self.add_fluent_resource(catalogs, language, id, content, false).map(|_| ())
+      },
+      #[allow(unreachable_patterns)]
+      _ => Err(I18nError::FormatNotEnabled { format }),
+    }
+  }
+
+  #[cfg(feature = "fluent")]
+  fn add_fluent_resource(
+    &self,
+    catalogs: &Catalogs,
+    language: &str,
+    id: &str,
+    content: &str,
+    overriding: bool,
+  ) -> Result<Vec<Override>, I18nError> {
+    let use_isolating = self.get_use_isolating();
+    let overrides =
+      catalogs.fluent.add_resource(language, id, content, overriding, use_isolating)?;
+    // Messages may have been fixed; report them again if they still fail.
+    self.inner.format_errors.lock().unwrap().reported.clear();
+    self.changed(catalogs);
+    Ok(overrides)
+  }
+
+  /// See [`load_translations_from_path`](crate::load_translations_from_path).
+  pub fn load_translations_from_path(&self, path: impl AsRef<str>) -> Result<(), I18nError> {
+    self.load_from_source(&FileSource::new(path.as_ref()))
+  }
+
+  /// See [`load_translations_from_path_with_layout`](crate::load_translations_from_path_with_layout).
+  pub fn load_translations_from_path_with_layout(
+    &self,
+    path: impl AsRef<Path>,
+    layout: Layout,
+  ) -> Result<(), I18nError> {
+    self.load_from_source(&FileSource::new(path.as_ref()).layout(layout))
+  }
+
+  /// See [`load_translations_from_path_with_report`](crate::load_translations_from_path_with_report).
+  pub fn load_translations_from_path_with_report(
+    &self,
+    path: impl AsRef<Path>,
+    mode: LoadMode,
+  ) -> LoadReport {
+    self.load_from_source_with_report(&FileSource::new(path.as_ref()), mode)
+  }
+
+  /// Load the translations at `path` like
+  /// [`load_translations_from_path`](Self::load_translations_from_path) and
+  /// reload them when they change; see [`Watcher`](crate::Watcher).
+  #[cfg(feature = "hot-reload")]
+  pub fn watch_path(&self, path: impl AsRef<Path>) -> Result<crate::Watcher, I18nError> {
+    crate::Watcher::new(self, FileSource::new(path.as_ref()))
+  }
+
+  /// See [`load_embedded`](crate::load_embedded).
+  pub fn load_embedded(&self, embedded: &Embedded) -> Result<(), I18nError> {
+    self.load_from_source(embedded)
+  }
+
+  /// See [`load_from_source`](crate::load_from_source).
+  pub fn load_from_source(&self, source: &impl TranslationSource) -> Result<(), I18nError> {
+    first_failure(self.load_from_source_with_report(source, LoadMode::Lenient))
+  }
+
+  /// See [`load_from_source_with_report`](crate::load_from_source_with_report).
+  pub fn load_from_source_with_report(
+    &self,
+    source: &impl TranslationSource,
+    mode: LoadMode,
+  ) -> LoadReport {
+    self.load_into("", source, mode)
+  }
+
+  /// Load the entries of `source` into `domain`, or into the domain below it
+  /// that an entry names.
+  fn load_into(&self, domain: &str, source: &impl TranslationSource, mode: LoadMode) -> LoadReport {
+    let (entries, failed) = source.entries_with_errors();
+    let mut report = LoadReport { loaded: vec![], failed };
+    let mut groups: Vec<(String, Vec<&SourceEntry>)> = vec![];
+    for entry in &entries {
+      let name = subdomain(domain, &entry.domain);
+      match groups.iter_mut().find(|(group, _)| *group == name) {
+        Some((_, group)) => group.push(entry),
+        None => groups.push((name, vec![entry])),
+      }
+    }
+    if mode == LoadMode::Strict {
+      for (name, group) in &groups {
+        let catalogs =
+          self.catalogs(name).unwrap_or_else(|| Arc::new(Catalogs::empty_like(&self.inner.core)));
+        report.failed.extend(self.check_entries(&catalogs, group));
+      }
+      if !report.is_ok() {
+        return report;
+      }
+    }
+    for (name, group) in groups {
+      let catalogs = self.create_domain(&name);
+      let loaded = report.loaded.len();
+      for entry in group {
+        match self.load_entry(&catalogs, entry) {
+          Ok(()) => report.loaded.push(PathBuf::from(entry.id.as_ref())),
+          Err(error) => report.failed.push(error),
+        }
+      }
+      if report.loaded.len() == loaded {
+        self.drop_empty_domain(&name);
+      }
+    }
+    report
+  }
+
+  /// The errors loading `entries` into `catalogs` would raise, found without
+  /// changing them.
+  fn check_entries(&self, catalogs: &Catalogs, entries: &[&SourceEntry]) -> Vec<I18nError> {
+    #[cfg(feature = "fluent")]
+    let (fluent, use_isolating) = (catalogs.fluent.duplicate(), self.get_use_isolating());
+    #[cfg(not(feature = "fluent"))]
+    let _ = catalogs;
+    let mut errors = vec![];
+    for entry in entries {
+      match entry.text() {
+        Err(error) => errors.push(error),
+        #[cfg(feature = "fluent")]
+        Ok(text) if backend_format(entry.format) == Format::Fluent => {
+          if let Err(error) =
+            fluent.add_resource(&entry.language, &entry.id, text, false, use_isolating)
+          {
+            errors.push(error.with_path(Path::new(entry.id.as_ref())));
+          }
+        },
+        #[cfg(feature = "classic")]
+        Ok(text) if backend_format(entry.format) == Format::Classic => {
+          let parse_errors = crate::check_translations(text);
+          if !parse_errors.is_empty() {
+            let language = entry.language.to_string();
+            let path = Some(PathBuf::from(entry.id.as_ref()));
+            errors.push(I18nError::Parse { language, path, errors: parse_errors });
+          }
+        },
+        Ok(_) => {},
+      }
+    }
+    errors
+  }
+
+  /// Load one translation file for `language` into `domain`.
+  #[cfg(feature = "hot-reload")]
+  pub(crate) fn load_file(
+    &self,
+    file: &Path,
+    domain: &str,
+    language: &str,
+  ) -> Result<(), I18nError> {
+    let entry = FileSource::entry(file, domain, language)?;
+    let result = self.load_entry(&self.create_domain(domain), &entry);
+    if result.is_err() {
+      self.drop_empty_domain(domain);
+    }
+    result
+  }
+
+  fn load_entry(&self, catalogs: &Catalogs, entry: &SourceEntry) -> Result<(), I18nError> {
+    let format = backend_format(entry.format);
+    // Each entry is its own Fluent resource, so files for the same language
+    // add up and reloading one replaces only its own messages.
+    self
+      .load_resource(catalogs, &entry.language, &entry.id, entry.text()?, format)
+      .map_err(|e| e.with_path(Path::new(entry.id.as_ref())))
+  }
+
+  /// Unload what [`load_file`](Self::load_file) loaded from `file`.
+  /// Returns whether anything was unloaded.
+  #[cfg(feature = "hot-reload")]
+  pub(crate) fn unload_file(&self, file: &Path, domain: &str, language: &str) -> bool {
+    let Some(catalogs) = self.catalogs(domain) else {
+      return false;
+    };
+    let id = file.to_string_lossy();
+    let mut removed = false;
+    #[cfg(feature = "classic")]
+    {
+      removed |= catalogs.classic.remove_resource(language, &id);
+    }
+    #[cfg(feature = "fluent")]
+    {
+      let use_isolating = self.get_use_isolating();
+      removed |= catalogs.fluent.remove_resource(language, &id, use_isolating);
+    }
+    self.changed(&catalogs);
+    removed
+  }
+
+  // -------------------------------------------------------------------------
+  // Domains
+  // -------------------------------------------------------------------------
+
+  /// Load `source` into `domain` like
+  /// [`load_from_source`](Self::load_from_source) loads the core domain.
+  ///
+  /// A domain has its own catalogs, so its keys never collide with those of
+  /// the core domain or of other domains; [`translate_in`](Self::translate_in)
+  /// looks them up. The domain is created by its first load. The empty name
+  /// is the core domain. Entries that name a domain of their own load below
+  /// `domain`, e.g. into `plugin-x/export`.
+  pub fn load_domain_from_source(
+    &self,
+    domain: &str,
+    source: &impl TranslationSource,
+  ) -> Result<(), I18nError> {
+    first_failure(self.load_domain_from_source_with_report(domain, source, LoadMode::Lenient))
+  }
+
+  /// Like [`load_domain_from_source`](Self::load_domain_from_source), but
+  /// reports every entry; see
+  /// [`load_from_source_with_report`](crate::load_from_source_with_report).
+  pub fn load_domain_from_source_with_report(
+    &self,
+    domain: &str,
+    source: &impl TranslationSource,
+    mode: LoadMode,
+  ) -> LoadReport {
+    self.load_into(domain, source, mode)
+  }
+
+  /// Load `content` in the default [`Format`] into `domain`, e.g. a catalog
+  /// a plugin embeds with `include_str!`. Loading text for the same language
+  /// again replaces it.
+  pub fn load_domain_translations_from_text(
+    &self,
+    domain: impl AsRef<str>,
+    language: impl AsRef<str>,
+    content: impl AsRef<str>,
+  ) -> Result<(), I18nError> {
+    let source = MemorySource::new().add(language.as_ref(), Format::default(), content.as_ref());
+    self.load_domain_from_source(domain.as_ref(), &source)
+  }
+
+  /// Drop `domain` and all its catalogs. Returns whether it was loaded. The
+  /// core domain cannot be unloaded.
+  pub fn unload_domain(&self, domain: &str) -> bool {
+    let removed = self.inner.domains.write().unwrap().remove(domain).is_some();
+    self.inner.cache.invalidate();
+    removed
+  }
+
+  /// The names of the loaded domains, sorted.
+  pub fn domains(&self) -> Vec<String> {
+    let mut domains: Vec<String> = self.inner.domains.read().unwrap().keys().cloned().collect();
+    domains.sort();
+    domains
+  }
+
+  /// The languages loaded into `domain`, like [`languages`](Self::languages)
+  /// for the core domain.
+  pub fn domain_languages(&self, domain: &str) -> Vec<String> {
+    match domain {
+      "" => self.languages(),
+      _ => self.domain(domain).map(|catalogs| catalogs.languages()).unwrap_or_default(),
+    }
+  }
+
+  /// Translate `key` from `domain`, as `tr!(domain = "…", key)` does.
+  ///
+  /// The whole [`language_chain`](Self::language_chain) is searched in the
+  /// domain first, so a domain's own messages win over core messages with the
+  /// same key, even in a fallback language. Keys the domain does not define
+  /// fall back to the core domain, as does an unknown domain.
+  pub fn translate_in(&self, domain: &str, key: &str, args: &Args) -> String {
+    match self.inner.cache.key(args) {
+      Some(cache_args) => self.translate_cached(domain, key, args, cache_args).to_string(),
+      None => self.lookup(domain, key, args).0,
+    }
+  }
+
+  /// A named domain, `None` for the core domain or an unknown one.
+  fn domain(&self, domain: &str) -> Option<Arc<Catalogs>> {
+    if domain.is_empty() {
+      return None;
+    }
+    self.inner.domains.read().unwrap().get(domain).cloned()
+  }
+
+  /// The catalogs of `domain`, including the core domain.
+  fn catalogs(&self, domain: &str) -> Option<Arc<Catalogs>> {
+    match domain {
+      "" => Some(self.inner.core.clone()),
+      _ => self.domain(domain),
+    }
+  }
+
+  /// The catalogs of `domain`, created empty if it is not loaded yet.
+  fn create_domain(&self, domain: &str) -> Arc<Catalogs> {
+    if domain.is_empty() {
+      return self.inner.core.clone();
+    }
+    let mut domains = self.inner.domains.write().unwrap();
+    let catalogs = domains
+      .entry(domain.to_string())
+      .or_insert_with(|| Arc::new(Catalogs::empty_like(&self.inner.core)));
+    catalogs.clone()
+  }
+
+  /// Drop `domain` again when nothing could be loaded into it.
+  fn drop_empty_domain(&self, domain: &str) {
+    let mut domains = self.inner.domains.write().unwrap();
+    if domains.get(domain).is_some_and(|catalogs| catalogs.languages().is_empty()) {
+      domains.remove(domain);
+    }
+  }
+
+  /// Translate `key` through every compiled-in backend.
+  ///
+  /// Languages are tried in [`language_chain`](Self::language_chain) order.
+  /// With the `fluent` feature, the Fluent bundle of each language is
+  /// consulted before its classic catalog, so a key that has been migrated to
+  /// `.ftl` wins over its `.egl` original. For Fluent, `key` may also name an
+  /// attribute (`login-button.tooltip`) or a term (`-brand-name`). Fluent
+  /// formatting errors are passed to the
+  /// [format error hook](Self::set_format_error_hook).
+  ///
+  /// With the [cache](Self::set_cache_enabled) enabled, repeated lookups of
+  /// the same key and arguments are served from it.
+  pub fn translate(&self, key: &str, args: &Args) -> String {
+    self.translate_in("", key, args)
+  }
+
+  /// Translate `key` in message context `context`, as
+  /// `tr!(ctx: "menu", "Open")` does, so the same source text can have a
+  /// translation per context. The message is looked up by its
+  /// [`context_key`](crate::context_key), written `[menu] Open = …` in a
+  /// `.egl` file. Without a translation for the context, `key` is translated
+  /// as if it had none.
+  pub fn translate_ctx(&self, context: &str, key: &str, args: &Args) -> String {
+    self.translate(&crate::context_key(context, key), args)
+  }
+
+  /// Like [`translate`](Self::translate), but returns the result shared with
+  /// the [cache](Self::set_cache_enabled), so a cache hit does not allocate.
+  pub fn translate_shared(&self, key: &str, args: &Args) -> Arc<str> {
+    match self.inner.cache.key(args) {
+      Some(cache_args) => self.translate_cached("", key, args, cache_args),
+      None => self.lookup("", key, args).0.into(),
+    }
+  }
+
+  fn translate_cached(
+    &self,
+    domain: &str,
+    key: &str,
+    args: &Args,
+    cache_args: CacheKey,
+  ) -> Arc<str> {
+    // Domain names are not expected to contain NUL.
+    let cache_key = match domain {
+      "" => Cow::Borrowed(key),
+      _ => Cow::Owned(format!("{}\0{}", domain, key)),
+    };
+    let generation = match self.inner.cache.get(&cache_key, &cache_args) {
+      Ok(value) => return value,
+      Err(generation) => generation,
+    };
+    let (value, cacheable) = self.lookup(domain, key, args);
+    let value: Arc<str> = value.into();
+    if cacheable {
+      self.inner.cache.insert(generation, &cache_key, cache_args, value.clone());
+    }
+    value
+  }
+
+  /// Memoize [`translate`](Self::translate) results by key and arguments.
+  /// Off by default.
+  ///
+  /// egui runs every `tr!` again each frame; with the cache a repeated call
+  /// skips the lookup and formatting. Changing the language or fallbacks and
+  /// loading or removing catalogs empties it. Missing keys and Fluent
+  /// messages that failed to format are not cached, so they are still
+  /// reported every time.
+  pub fn set_cache_enabled(&self, enabled: bool) {
+    self.inner.cache.set_enabled(enabled);
+  }
+
+  pub fn is_cache_enabled(&self) -> bool {
+    self.inner.cache.is_enabled()
+  }
+
+  /// Hits and misses since the cache was last [cleared](Self::clear_cache).
+  pub fn cache_stats(&self) -> CacheStats {
+    self.inner.cache.stats()
+  }
+
+  /// Empty the cache and reset its [statistics](Self::cache_stats).
+  pub fn clear_cache(&self) {
+    self.inner.cache.clear();
+  }
+
+  /// The uncached lookup behind [`translate`](Self::translate), and whether
+  /// its result may be cached. A [`context_key`](crate::context_key) that
+  /// is not found is tried again without its context; only when that fails
+  /// too is it recorded as missing, under the context key.
+  fn lookup(&self, domain: &str, key: &str, args: &Args) -> (String, bool) {
+    let found = self.find_value(domain, key, args).or_else(|| {
+      let (_, key) = crate::split_context_key(key)?;
+      self.find_value(domain, key, args)
+    });
+    found.unwrap_or_else(|| (self.missing(domain, key), false))
+  }
+
+  #[cfg(feature = "fluent")]
+  fn find_value(&self, domain: &str, key: &str, args: &Args) -> Option<(String, bool)> {
+    let (language, (value, errors)) = self.find_translation(domain, key, args)?;
+    let cacheable = errors.is_empty();
+    self.report_format_errors(&language, key, errors);
+    Some((value, cacheable))
+  }
+
+  #[cfg(all(feature = "classic", not(feature = "fluent")))]
+  fn find_value(&self, domain: &str, key: &str, args: &Args) -> Option<(String, bool)> {
+    let (_, value) = self.find_in(domain, |catalogs, language| {
+      non_empty(catalogs.classic.translate(language, key, args.classic()))
+    })?;
+    Some((value, true))
+  }
+
+  #[cfg(not(any(feature = "classic", feature = "fluent")))]
+  fn find_value(&self, _: &str, _: &str, _: &Args) -> Option<(String, bool)> {
+    None
+  }
+
+  /// Like [`translate`](Self::translate), but returns the Fluent formatting
+  /// errors instead of passing them to the hook. The list is empty when the
+  /// message formatted cleanly, came from a classic catalog or was not found.
+  #[cfg(feature = "fluent")]
+  pub fn try_translate(&self, key: &str, args: &Args) -> (String, Vec<FluentError>) {
+    match self.find_translation("", key, args) {
+      Some((_, found)) => found,
+      None => (self.missing("", key), vec![]),
+    }
+  }
+
+  /// Translate `key` through the classic catalogs only. The arguments are
+  /// strings; typed placeholders such as `{count:number}` parse them.
+  #[cfg(feature = "classic")]
+  pub fn translate_classic(&self, key: &str, args: &HashMap<&str, String>) -> String {
+    let args: HashMap<&str, ArgValue> =
+      args.iter().map(|(name, value)| (*name, ArgValue::String(value.clone()))).collect();
+    self
+      .find_in("", |catalogs, language| non_empty(catalogs.classic.translate(language, key, &args)))
+      .map(|(_, value)| value)
+      .unwrap_or_else(|| self.missing("", key))
+  }
+
+  /// Translate `key` through the Fluent bundles only.
+  #[cfg(feature = "fluent")]
+  pub fn translate_fluent(&self, key: &str, args: &FluentArgs) -> String {
+    let found = self.find_in("", |catalogs, language| {
+      catalogs.fluent.translate(language, key, args).filter(|(value, _)| !value.is_empty())
+    });
+    match found {
+      Some((language, (value, errors))) => {
+        self.report_format_errors(&language, key, errors);
+        value
+      },
+      None => self.missing("", key),
+    }
+  }
+
+  /// The attribute names of Fluent message `key`, in source order, from the
+  /// first catalog in the [`language_chain`](Self::language_chain) that has
+  /// the message. Each can be looked up as `key.attribute`.
+  #[cfg(feature = "fluent")]
+  pub fn message_attributes(&self, key: &str) -> Vec<String> {
+    self
+      .find_in("", |catalogs, language| catalogs.fluent.attributes(language, key))
+      .map(|(_, attributes)| attributes)
+      .unwrap_or_default()
+  }
+
+  /// The lookup behind [`translate`](Self::translate) and
+  /// [`try_translate`](Self::try_translate).
+  #[cfg(feature = "fluent")]
+  fn find_translation(
+    &self,
+    domain: &str,
+    key: &str,
+    args: &Args,
+  ) -> Option<(String, (String, Vec<FluentError>))> {
+    self.find_in(domain, |catalogs, language| {
+      let fluent = catalogs.fluent.translate(language, key, args.fluent());
+      if let Some(found) = fluent.filter(|(value, _)| !value.is_empty()) {
+        return Some(found);
+      }
+      #[cfg(feature = "classic")]
+      if let Some(value) = non_empty(catalogs.classic.translate(language, key, args.classic())) {
+        return Some((value, vec![]));
+      }
+      None
+    })
+  }
+
+  /// Record `key` as missing and return what the
+  /// [missing-key policy](Self::set_missing_key_policy) says to show instead.
+  fn missing(&self, domain: &str, key: &str) -> String {
+    let (policy, language, chain) = {
+      let config = self.inner.config.read().unwrap();
+      (config.missing_key_policy.clone(), config.language.clone(), config.chain.to_vec())
+    };
+    let missing = MissingKey { key: key.to_string(), domain: domain.to_string(), language, chain };
+    crate::missing::record(&missing);
+    policy.resolve(&missing)
+  }
+
+  /// [`find`](Self::find) in `domain`, then in the core domain.
+  fn find_in<T>(
+    &self,
+    domain: &str,
+    mut lookup: impl FnMut(&Catalogs, &str) -> Option<T>,
+  ) -> Option<(String, T)> {
+    if let Some(catalogs) = self.domain(domain) {
+      if let Some(found) = self.find(&catalogs, |language| lookup(&catalogs, language)) {
+        return Some(found);
+      }
+    }
+    let core = &self.inner.core;
+    self.find(core, |language| lookup(core, language))
+  }
+
+  /// Run `lookup` for every language of `catalogs` matching the
+  /// [`language_chain`](Self::language_chain), in chain order, returning the
+  /// first result together with the name of the catalog it came from.
+  fn find<T>(
+    &self,
+    catalogs: &Catalogs,
+    mut lookup: impl FnMut(&str) -> Option<T>,
+  ) -> Option<(String, T)> {
+    let chain = self.inner.config.read().unwrap().chain.clone();
+    if chain.is_empty() {
+      return None;
+    }
+    let loaded = catalogs.index.read().unwrap();
+    chain
+      .iter()
+      .flat_map(|tag| loaded.iter().filter(move |(normalized, _)| normalized == tag))
+      .find_map(|(_, language)| lookup(language).map(|found| (language.clone(), found)))
+  }
+
+  #[cfg(feature = "fluent")]
+  fn report_format_errors(&self, language: &str, key: &str, errors: Vec<FluentError>) {
+    if errors.is_empty() {
+      return;
+    }
+    let hook = self.get_format_error_hook();
+    if let FormatErrorHook::Ignore = hook {
+      return;
+    }
+    let report = FormatErrors { language: language.to_string(), key: key.to_string(), errors };
+    // `tr!` runs every frame; only the first occurrence is logged or kept.
+    let first_report = || {
+      let entry = (report.language.clone(), report.key.clone());
+      self.inner.format_errors.lock().unwrap().reported.insert(entry)
+    };
+    match hook {
+      FormatErrorHook::Ignore => {},
+      FormatErrorHook::Log => {
+        if first_report() {
+          log::warn!("{}", report);
+        }
+      },
+      FormatErrorHook::PanicInDebug => {
+        if cfg!(debug_assertions) {
+          panic!("{}", report);
+        }
+        if first_report() {
+          log::warn!("{}", report);
+        }
+      },
+      FormatErrorHook::Collect => {
+        if first_report() {
+          self.inner.format_errors.lock().unwrap().collected.push(report);
+        }
+      },
+      FormatErrorHook::Custom(hook) => hook(&report),
+    }
+  }
+}
+
+/// The first failure of `report`, logging the others.
+fn first_failure(report: LoadReport) -> Result<(), I18nError> {
+  for error in report.failed.iter().skip(1) {
+    log::warn!("failed to load translations: {}", error);
+  }
+  report.into_result()
+}
+
+/// A format whose backend is not compiled in goes to the default one, as
+/// files always did before both could coexist.
+fn backend_format(format: Format) -> Format {
+  Some(format).filter(Format::is_enabled).unwrap_or_default()
+}
+
+#[cfg(feature = "classic")]
+fn non_empty(value: Option<String>) -> Option<String> {
+  value.filter(|value| !value.is_empty())
+}
@@ -0,0 +1,612 @@
+//! Locale-aware number and date formatting shared by the backends.
+//!
+//! This covers the conventions of common locales — separators, currency and
+//! percent placement, numeric date order, 12/24-hour clocks and, for a few
+//! languages, month names. It is not a replacement for ICU; other locales get
+//! neutral defaults (`1,234.5`, `2025-01-31`, `15:04`).
+
+use unic_langid::LanguageIdentifier;
+
+// ---------------------------------------------------------------------------
+// Locale conventions
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq)]
+enum CurrencyPosition {
+  /// `$1.50`
+  Before,
+  /// `R$ 1,50`
+  BeforeSpace,
+  /// `1,50 €`
+  AfterSpace,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DateOrder {
+  /// `1/31/2025`
+  MonthDayYear,
+  /// `31/01/2025`
+  DaySlash,
+  /// `31.01.2025`
+  DayDot,
+  /// `31-01-2025`
+  DayDash,
+  /// `2025/01/31`
+  YearSlash,
+  /// `2025-01-31`
+  Iso,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum LongDate {
+  /// `January 31, 2025`
+  MonthFirst,
+  /// `31 January 2025`
+  DayFirst,
+  /// `31. Januar 2025`
+  DayDotFirst,
+  /// `31 de enero de 2025`
+  DayDe,
+}
+
+struct Conventions {
+  decimal: &'static str,
+  group: &'static str,
+  currency: CurrencyPosition,
+  percent: &'static str,
+  date: DateOrder,
+  long_date: LongDate,
+  months: Option<&'static [&'static str; 12]>,
+  hour12: bool,
+}
+
+const MONTHS_EN: [&str; 12] = [
+  "January",
+  "February",
+  "March",
+  "April",
+  "May",
+  "June",
+  "July",
+  "August",
+  "September",
+  "October",
+  "November",
+  "December",
+];
+const MONTHS_DE: [&str; 12] = [
+  "Januar",
+  "Februar",
+  "März",
+  "April",
+  "Mai",
+  "Juni",
+  "Juli",
+  "August",
+  "September",
+  "Oktober",
+  "November",
+  "Dezember",
+];
+const MONTHS_FR: [&str; 12] = [
+  "janvier",
+  "février",
+  "mars",
+  "avril",
+  "mai",
+  "juin",
+  "juillet",
+  "août",
+  "septembre",
+  "octobre",
+  "novembre",
+  "décembre",
+];
+const MONTHS_ES: [&str; 12] = [
+  "enero",
+  "febrero",
+  "marzo",
+  "abril",
+  "mayo",
+  "junio",
+  "julio",
+  "agosto",
+  "septiembre",
+  "octubre",
+  "noviembre",
+  "diciembre",
+];
+const MONTHS_IT: [&str; 12] = [
+  "gennaio",
+  "febbraio",
+  "marzo",
+  "aprile",
+  "maggio",
+  "giugno",
+  "luglio",
+  "agosto",
+  "settembre",
+  "ottobre",
+  "novembre",
+  "dicembre",
+];
+const MONTHS_PT: [&str; 12] = [
+  "janeiro",
+  "fevereiro",
+  "março",
+  "abril",
+  "maio",
+  "junho",
+  "julho",
+  "agosto",
+  "setembro",
+  "outubro",
+  "novembro",
+  "dezembro",
+];
+const MONTHS_NL: [&str; 12] = [
+  "januari",
+  "februari",
+  "maart",
+  "april",
+  "mei",
+  "juni",
+  "juli",
+  "augustus",
+  "september",
+  "oktober",
+  "november",
+  "december",
+];
+
+fn conventions(locale: &LanguageIdentifier) -> Conventions {
+  let region = locale.region.as_ref().map(|r| r.as_str());
+  let mut c = Conventions {
+    decimal: ".",
+    group: ",",
+    currency: CurrencyPosition::Before,
+    percent: "%",
+    date: DateOrder::Iso,
+    long_date: LongDate::DayFirst,
+    months: None,
+    hour12: false,
+  };
+  match locale.language.as_str() {
+    "en" => {
+      let us_style = matches!(region, None | Some("US") | Some("PH"));
+      c.date = if us_style { DateOrder::MonthDayYear } else { DateOrder::DaySlash };
+      c.long_date = if us_style { LongDate::MonthFirst } else { LongDate::DayFirst };
+      c.months = Some(&MONTHS_EN);
+      c.hour12 = matches!(region, None | Some("US" | "PH" | "CA" | "AU" | "NZ" | "IN"));
+    },
+    "de" => {
+      (c.decimal, c.group) = if region == Some("CH") { (".", "’") } else { (",", ".") };
+      c.currency = CurrencyPosition::AfterSpace;
+      c.percent = "\u{a0}%";
+      c.date = DateOrder::DayDot;
+      c.long_date = LongDate::DayDotFirst;
+      c.months = Some(&MONTHS_DE);
+    },
+    "fr" => {
+      (c.decimal, c.group) =
+        if region == Some("CH") { (".", "\u{202f}") } else { (",", "\u{202f}") };
+      c.currency = CurrencyPosition::AfterSpace;
+      c.percent = "\u{a0}%";
+      c.date = DateOrder::DaySlash;
+      c.months = Some(&MONTHS_FR);
+    },
+    "es" => {
+      let americas = matches!(region, Some("MX" | "US" | "419"));
+      (c.decimal, c.group) = if americas { (".", ",") } else { (",", ".") };
+      c.currency = if americas { CurrencyPosition::Before } else { CurrencyPosition::AfterSpace };
+      c.percent = if americas { "%" } else { "\u{a0}%" };
+      c.date = DateOrder::DaySlash;
+      c.long_date = LongDate::DayDe;
+      c.months = Some(&MONTHS_ES);
+    },
+    "it" => {
+      (c.decimal, c.group) = (",", ".");
+      c.currency = CurrencyPosition::AfterSpace;
+      c.date = DateOrder::DaySlash;
+      c.months = Some(&MONTHS_IT);
+    },
+    "pt" => {
+      (c.decimal, c.group) = (",", ".");
+      c.currency = if region == Some("PT") {
+        CurrencyPosition::AfterSpace
+      } else {
+        CurrencyPosition::BeforeSpace
+      };
+      c.date = DateOrder::DaySlash;
+      c.long_date = LongDate::DayDe;
+      c.months = Some(&MONTHS_PT);
+    },
+    "nl" => {
+      (c.decimal, c.group) = (",", ".");
+      c.currency = CurrencyPosition::BeforeSpace;
+      c.date = DateOrder::DayDash;
+      c.months = Some(&MONTHS_NL);
+    },
+    "da" => {
+      (c.decimal, c.group) = (",", ".");
+      c.currency = CurrencyPosition::AfterSpace;
+      c.percent = "\u{a0}%";
+      c.date = DateOrder::DayDot;
+    },
+    "ru" | "uk" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "bg" => {
+      (c.decimal, c.group) = (",", "\u{a0}");
+      c.currency = CurrencyPosition::AfterSpace;
+      c.percent = "\u{a0}%";
+      c.date = DateOrder::DayDot;
+    },
+    "sv" | "hu" | "lt" => {
+      (c.decimal, c.group) = (",", "\u{a0}");
+      c.currency = CurrencyPosition::AfterSpace;
+      c.percent = "\u{a0}%";
+    },
+    "tr" => {
+      (c.decimal, c.group) = (",", ".");
+      c.date = DateOrder::DayDot;
+    },
+    "ja" | "zh" => c.date = DateOrder::YearSlash,
+    "ko" => c.date = DateOrder::Iso,
+    "hi" => {
+      c.date = DateOrder::DaySlash;
+      c.hour12 = true;
+    },
+    _ => {},
+  }
+  c
+}
+
+// ---------------------------------------------------------------------------
+// Numbers
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CurrencyDisplay {
+  /// `$`, `€`, …; the ISO code for currencies without a well-known symbol.
+  Symbol,
+  /// `USD`, `EUR`, …
+  Code,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum NumberStyle {
+  Decimal,
+  /// The value is a fraction: `0.25` is `25%`.
+  Percent,
+  /// `code` is an ISO 4217 code such as `EUR`.
+  Currency {
+    code: String,
+    display: CurrencyDisplay,
+  },
+}
+
+/// The options of `Intl.NumberFormat` / Fluent's `NUMBER`, minus those that
+/// only matter for plural selection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NumberOptions {
+  pub style: NumberStyle,
+  pub grouping: bool,
+  pub min_integer_digits: usize,
+  pub min_fraction_digits: Option<usize>,
+  pub max_fraction_digits: Option<usize>,
+  pub min_significant_digits: Option<usize>,
+  pub max_significant_digits: Option<usize>,
+}
+
+impl Default for NumberOptions {
+  fn default() -> Self {
+    Self {
+      style: NumberStyle::Decimal,
+      grouping: true,
+      min_integer_digits: 1,
+      min_fraction_digits: None,
+      max_fraction_digits: None,
+      min_significant_digits: None,
+      max_significant_digits: None,
+    }
+  }
+}
+
+fn currency_symbol(code: &str) -> Option<&'static str> {
+  Some(match code {
+    "USD" => "$",
+    "EUR" => "€",
+    "GBP" => "£",
+    "JPY" | "CNY" => "¥",
+    "INR" => "₹",
+    "KRW" => "₩",
+    "RUB" => "₽",
+    "BRL" => "R$",
+    "TRY" => "₺",
+    _ => return None,
+  })
+}
+
+fn currency_fraction_digits(code: &str) -> usize {
+  match code {
+    "JPY" | "KRW" | "VND" | "CLP" | "ISK" => 0,
+    _ => 2,
+  }
+}
+
+/// Format `value` the way `locale` writes numbers.
+pub(crate) fn format_number(
+  locale: &LanguageIdentifier,
+  value: f64,
+  options: &NumberOptions,
+) -> String {
+  let c = conventions(locale);
+  if value.is_nan() {
+    return "NaN".to_string();
+  }
+
+  let (value, default_min, default_max) = match &options.style {
+    NumberStyle::Decimal => (value, 0, 3),
+    NumberStyle::Percent => (value * 100.0, 0, 0),
+    NumberStyle::Currency { code, .. } => {
+      let digits = currency_fraction_digits(code);
+      (value, digits, digits)
+    },
+  };
+  let negative = value.is_sign_negative();
+  let (integer, fraction) = if value.is_infinite() {
+    ("∞".to_string(), String::new())
+  } else if options.min_significant_digits.is_some() || options.max_significant_digits.is_some() {
+    let min = options.min_significant_digits.unwrap_or(1).max(1);
+    let max = options.max_significant_digits.unwrap_or(21).max(min);
+    significant_digits(value.abs(), min, max)
+  } else {
+    let min = options.min_fraction_digits.unwrap_or(default_min);
+    let max = options.max_fraction_digits.unwrap_or(default_max.max(min)).max(min);
+    fraction_digits(value.abs(), min, max)
+  };
+
+  let integer = format!("{:0>width$}", integer, width = options.min_integer_digits);
+  let mut number = if options.grouping { group(&integer, c.group) } else { integer };
+  if !fraction.is_empty() {
+    number.push_str(c.decimal);
+    number.push_str(&fraction);
+  }
+  // No `-0`: the sign only shows when a non-zero digit does.
+  let sign =
+    if negative && number.chars().any(|d| d.is_ascii_digit() && d != '0') { "-" } else { "" };
+
+  match &options.style {
+    NumberStyle::Decimal => format!("{}{}", sign, number),
+    NumberStyle::Percent => format!("{}{}{}", sign, number, c.percent),
+    NumberStyle::Currency { code, display } => {
+      let symbol = match display {
+        CurrencyDisplay::Symbol => currency_symbol(code),
+        CurrencyDisplay::Code => None,
+      };
+      match (c.currency, symbol) {
+        (CurrencyPosition::Before, Some(symbol)) => format!("{}{}{}", sign, symbol, number),
+        (CurrencyPosition::Before | CurrencyPosition::BeforeSpace, symbol) => {
+          format!("{}{}\u{a0}{}", sign, symbol.unwrap_or(code), number)
+        },
+        (CurrencyPosition::AfterSpace, symbol) => {
+          format!("{}{}\u{a0}{}", sign, number, symbol.unwrap_or(code))
+        },
+      }
+    },
+  }
+}
+
+/// `value` rounded to at most `max` decimals, keeping at least `min`.
+fn fraction_digits(value: f64, min: usize, max: usize) -> (String, String) {
+  // Halves round away from zero, as `Intl.NumberFormat` does; `format!`
+  // alone rounds them to even.
+  let scale = 10f64.powi(max.min(15) as i32);
+  let rounded = (value * scale).round() / scale;
+  let value = if rounded.is_finite() { rounded } else { value };
+  let formatted = format!("{:.*}", max, value);
+  let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+  let mut fraction = fraction.to_string();
+  while fraction.len() > min && fraction.ends_with('0') {
+    fraction.pop();
+  }
+  (integer.to_string(), fraction)
+}
+
+/// `value` rounded to at most `max` significant digits, keeping at least
+/// `min`.
+fn significant_digits(value: f64, min: usize, max: usize) -> (String, String) {
+  if value == 0.0 {
+    return ("0".to_string(), "0".repeat(min - 1));
+  }
+  // Digits before the decimal point; zero or negative below 1.
+  let magnitude = value.log10().floor() as i64 + 1;
+  let decimals = max as i64 - magnitude;
+  if decimals < 0 {
+    let scale = 10f64.powi(-decimals as i32);
+    return (format!("{:.0}", (value / scale).round() * scale), String::new());
+  }
+  let min_decimals = (min as i64 - magnitude).max(0) as usize;
+  fraction_digits(value, min_decimals, decimals as usize)
+}
+
+/// Insert `separator` between groups of three digits.
+fn group(integer: &str, separator: &str) -> String {
+  let head = match integer.len() % 3 {
+    0 => 3.min(integer.len()),
+    n => n,
+  };
+  let (head, rest) = integer.split_at(head);
+  let mut grouped = head.to_string();
+  for digits in rest.as_bytes().chunks(3) {
+    grouped.push_str(separator);
+    grouped.push_str(std::str::from_utf8(digits).unwrap_or_default());
+  }
+  grouped
+}
+
+// ---------------------------------------------------------------------------
+// Dates and times
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DateStyle {
+  /// Numeric: `1/31/2025`, `31.01.2025`, …
+  Short,
+  /// With the month name where known: `January 31, 2025`.
+  Long,
+}
+
+impl DateStyle {
+  /// `Intl.DateTimeFormat`'s `dateStyle`; `medium` and `full` are long.
+  pub(crate) fn parse(value: &str) -> Option<Self> {
+    match value {
+      "short" => Some(Self::Short),
+      "medium" | "long" | "full" => Some(Self::Long),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum TimeStyle {
+  /// Hours and minutes.
+  Short,
+  /// Hours, minutes and seconds.
+  Medium,
+}
+
+impl TimeStyle {
+  /// `Intl.DateTimeFormat`'s `timeStyle`; `long` and `full` are medium.
+  pub(crate) fn parse(value: &str) -> Option<Self> {
+    match value {
+      "short" => Some(Self::Short),
+      "medium" | "long" | "full" => Some(Self::Medium),
+      _ => None,
+    }
+  }
+}
+
+/// Format a Unix `timestamp` (seconds) the way `locale` writes dates and
+/// times. No time zone conversion is applied. With neither style, the short
+/// date is written.
+pub(crate) fn format_datetime(
+  locale: &LanguageIdentifier,
+  timestamp: i64,
+  date: Option<DateStyle>,
+  time: Option<TimeStyle>,
+) -> String {
+  let c = conventions(locale);
+  let (year, month, day, hour, minute, second) = fields(timestamp);
+
+  let date = match (date, time) {
+    (None, None) => Some(DateStyle::Short),
+    (date, _) => date,
+  };
+  let date = date.map(|style| match (style, c.months) {
+    (DateStyle::Long, Some(months)) => {
+      let name = months[month as usize - 1];
+      match c.long_date {
+        LongDate::MonthFirst => format!("{} {}, {}", name, day, year),
+        LongDate::DayFirst => format!("{} {} {}", day, name, year),
+        LongDate::DayDotFirst => format!("{}. {} {}", day, name, year),
+        LongDate::DayDe => format!("{} de {} de {}", day, name, year),
+      }
+    },
+    _ => match c.date {
+      DateOrder::MonthDayYear => format!("{}/{}/{}", month, day, year),
+      DateOrder::DaySlash => format!("{:02}/{:02}/{}", day, month, year),
+      DateOrder::DayDot => format!("{:02}.{:02}.{}", day, month, year),
+      DateOrder::DayDash => format!("{:02}-{:02}-{}", day, month, year),
+      DateOrder::YearSlash => format!("{}/{:02}/{:02}", year, month, day),
+      DateOrder::Iso => format!("{}-{:02}-{:02}", year, month, day),
+    },
+  });
+  let time = time.map(|style| {
+    let seconds = match style {
+      TimeStyle::Short => String::new(),
+      TimeStyle::Medium => format!(":{:02}", second),
+    };
+    if c.hour12 {
+      let suffix = if hour < 12 { "AM" } else { "PM" };
+      let hour = if hour % 12 == 0 { 12 } else { hour % 12 };
+      format!("{}:{:02}{}\u{a0}{}", hour, minute, seconds, suffix)
+    } else {
+      format!("{:02}:{:02}{}", hour, minute, seconds)
+    }
+  });
+
+  match (date, time) {
+    (Some(date), Some(time)) if locale.language.as_str() == "en" => format!("{}, {}", date, time),
+    (Some(date), Some(time)) => format!("{} {}", date, time),
+    (Some(v), None) | (None, Some(v)) => v,
+    (None, None) => String::new(),
+  }
+}
+
+/// `timestamp` as ISO 8601: `2025-01-31T15:04:05Z`.
+#[cfg(feature = "classic")]
+pub(crate) fn iso_datetime(timestamp: i64) -> String {
+  let (year, month, day, hour, minute, second) = fields(timestamp);
+  format!("{}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, hour, minute, second)
+}
+
+/// Year, month, day, hour, minute and second of a Unix `timestamp`.
+fn fields(timestamp: i64) -> (i64, i64, i64, i64, i64, i64) {
+  let (year, month, day) = civil_from_days(timestamp.div_euclid(86_400));
+  let seconds = timestamp.rem_euclid(86_400);
+  (year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Parse `YYYY-MM-DD`, optionally followed by `THH:MM[:SS]` and `Z`, into a
+/// Unix timestamp. `None` for years too far out to fit.
+pub(crate) fn parse_timestamp(value: &str) -> Option<i64> {
+  let value = value.strip_suffix('Z').unwrap_or(value);
+  let (date, time) = match value.split_once(['T', ' ']) {
+    Some((date, time)) => (date, Some(time)),
+    None => (value, None),
+  };
+  let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+  let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+  if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+    return None;
+  }
+  let mut seconds = 0;
+  if let Some(time) = time {
+    let parts: Vec<i64> = time.split(':').map(str::parse).collect::<Result<_, _>>().ok()?;
+    let (hour, minute, second) = match parts[..] {
+      [h, m] => (h, m, 0),
+      [h, m, s] => (h, m, s),
+      _ => return None,
+    };
+    if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..61).contains(&second) {
+      return None;
+    }
+    seconds = hour * 3600 + minute * 60 + second;
+  }
+  days_from_civil(year, month, day)?.checked_mul(86_400)?.checked_add(seconds)
+}
+
+/// Days since 1970-01-01 to (year, month, day) in the proleptic Gregorian
+/// calendar (Howard Hinnant's `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  (year, month, day)
+}
+
+/// The inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let yoe = year.rem_euclid(400);
+  let mp = if month > 2 { month - 3 } else { month + 9 };
+  let doy = (153 * mp + 2) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era.checked_mul(146_097)?.checked_add(doe - 719_468)
+}
@@ -1,10 +1,48 @@
+// Only the `compile_error!` below should be reported without a backend.
+#![cfg_attr(not(any(feature = "classic", feature = "fluent")), allow(unused))]
+
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
-use std::sync::RwLock;
 
-pub use self::vendor::classic::parse_translations;
+#[cfg(feature = "classic")]
+pub use self::args::ArgValue;
+pub use self::args::Args;
+#[cfg(any(feature = "classic", feature = "fluent"))]
+pub use self::args::IntoArg;
+pub use self::cache::CacheStats;
+pub use self::embed::{Embedded, EmbeddedFile};
+#[doc(hidden)]
+pub use self::error::line_column;
+#[cfg(feature = "fluent")]
+pub use self::error::{FormatErrorHook, FormatErrors};
+pub use self::error::{I18nError, ParseError, SyntaxError};
+pub use self::format::Format;
+pub use self::instance::I18n;
+pub use self::missing::{
+  clear_missing_keys, dump_missing_keys, missing_keys, MissingKey, MissingKeyPolicy,
+};
+pub use self::source::{
+  FileSource, Layout, LoadMode, LoadReport, MemorySource, SourceEntry, TranslationSource,
+};
+pub use self::system::{EnvLanguages, LanguageSource, StaticLanguages};
+#[doc(hidden)]
+pub use self::vendor::classic::check_translations;
+#[cfg(feature = "classic")]
+pub use self::vendor::classic::placeholder_names;
+pub use self::vendor::classic::{
+  context_key, escape_key, escape_value, parse_translations, split_context_key,
+};
+#[cfg(feature = "fluent")]
+pub use self::vendor::fluent::Override;
+
+#[cfg(feature = "egui")]
+pub use self::context::I18nContextExt;
+
+#[cfg(feature = "hot-reload")]
+pub use self::watch::{Reload, Watcher};
+
+#[cfg(feature = "egui")]
+pub use egui;
 
 #[cfg(feature = "fluent")]
 pub use fluent;
@@ -12,52 +50,105 @@ pub use fluent;
 #[cfg(feature = "fluent")]
 pub use fluent_bundle;
 
+pub use unic_langid;
+
+mod args;
+mod cache;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+#[cfg(feature = "egui")]
+mod context;
+mod embed;
+mod error;
+mod format;
+mod instance;
+mod intl;
+pub mod locale;
+mod missing;
+mod source;
+mod system;
 mod vendor;
+#[cfg(feature = "hot-reload")]
+mod watch;
+
+#[cfg(not(any(feature = "classic", feature = "fluent")))]
+compile_error!("egui-i18n needs at least one of the `classic` and `fluent` features");
 
 // ---------------------------------------------------------------------------
-// Global configuration
+// Default instance
 // ---------------------------------------------------------------------------
 
-struct Config {
-  language: String,
-  fallback: String,
-  #[cfg(feature = "fluent")]
-  use_isolating: bool,
-}
-
-#[allow(clippy::derivable_impls)]
-impl Default for Config {
-  fn default() -> Self {
-    Self {
-      language: String::new(),
-      fallback: String::new(),
-      #[cfg(feature = "fluent")]
-      use_isolating: true,
-    }
-  }
-}
-
-// todo: migrate to egui context
-static CONFIG: Lazy<RwLock<Config>> = Lazy::new(|| RwLock::new(Config::default()));
+// Backs the free functions and `tr!` below; exposed as `I18n::global()`. Apps
+// that need several independently configured translators use `I18n` values
+// directly (or one per `egui::Context` through `I18nContextExt`).
+static DEFAULT: Lazy<I18n> = Lazy::new(I18n::new);
 
 // ---------------------------------------------------------------------------
 // Language / fallback configuration
 // ---------------------------------------------------------------------------
 
 pub fn set_language(locale: &str) {
-  CONFIG.write().unwrap().language = locale.to_string();
+  DEFAULT.set_language(locale);
 }
 
 pub fn get_language() -> String {
-  CONFIG.read().unwrap().language.clone()
+  DEFAULT.get_language()
 }
 
 pub fn set_fallback(locale: &str) {
-  CONFIG.write().unwrap().fallback = locale.to_string();
+  DEFAULT.set_fallback(locale);
 }
 
 pub fn get_fallback() -> String {
-  CONFIG.read().unwrap().fallback.clone()
+  DEFAULT.get_fallback()
+}
+
+/// Set an ordered list of fallback languages, tried after the active one.
+pub fn set_fallbacks(locales: &[impl AsRef<str>]) {
+  DEFAULT.set_fallbacks(locales);
+}
+
+pub fn get_fallbacks() -> Vec<String> {
+  DEFAULT.get_fallbacks()
+}
+
+/// The normalized language tags a lookup tries, in order. See
+/// [`locale::fallback_chain`].
+pub fn language_chain() -> Vec<String> {
+  DEFAULT.language_chain()
+}
+
+/// Choose what lookups return for keys no catalog has. Defaults to
+/// [`MissingKeyPolicy::Empty`].
+pub fn set_missing_key_policy(policy: MissingKeyPolicy) {
+  DEFAULT.set_missing_key_policy(policy);
+}
+
+pub fn get_missing_key_policy() -> MissingKeyPolicy {
+  DEFAULT.get_missing_key_policy()
+}
+
+// ---------------------------------------------------------------------------
+// Preferred-language detection
+// ---------------------------------------------------------------------------
+
+/// The user's preferred UI languages from the process environment (`LANGUAGE`,
+/// `LC_ALL`, `LC_MESSAGES`, `LANG`), most preferred first. See
+/// [`EnvLanguages`].
+pub fn system_languages() -> Vec<String> {
+  EnvLanguages::new().preferred_languages()
+}
+
+/// Set the language and fallbacks from the user's preferred languages,
+/// negotiated against the loaded ones; `default` is used when nothing matches
+/// and is always the last fallback. Returns the selected language.
+///
+/// ```ignore
+/// egui_i18n::load_translations_from_path("i18n/")?;
+/// egui_i18n::negotiate_language(&egui_i18n::EnvLanguages::new(), "en-US");
+/// ```
+pub fn negotiate_language(source: &impl LanguageSource, default: &str) -> String {
+  DEFAULT.negotiate_language(source, default)
 }
 
 // ---------------------------------------------------------------------------
@@ -66,80 +157,131 @@ pub fn get_fallback() -> String {
 
 #[cfg(feature = "fluent")]
 pub fn set_use_isolating(value: bool) {
-  CONFIG.write().unwrap().use_isolating = value;
+  DEFAULT.set_use_isolating(value);
 }
 
 #[cfg(feature = "fluent")]
 pub fn get_use_isolating() -> bool {
-  CONFIG.read().unwrap().use_isolating
+  DEFAULT.get_use_isolating()
 }
 
 // ---------------------------------------------------------------------------
-// Loaded-language enumeration
+// Fluent-only: formatting error reporting
 // ---------------------------------------------------------------------------
 
+/// Choose what `tr!` does when formatting a Fluent message raises errors.
+/// Defaults to [`FormatErrorHook::Log`].
 #[cfg(feature = "fluent")]
-pub fn languages() -> Vec<String> {
-  vendor::fluent::languages()
+pub fn set_format_error_hook(hook: FormatErrorHook) {
+  DEFAULT.set_format_error_hook(hook);
 }
 
-#[cfg(not(feature = "fluent"))]
+#[cfg(feature = "fluent")]
+pub fn get_format_error_hook() -> FormatErrorHook {
+  DEFAULT.get_format_error_hook()
+}
+
+/// Drain the errors gathered under [`FormatErrorHook::Collect`].
+#[cfg(feature = "fluent")]
+pub fn take_format_errors() -> Vec<FormatErrors> {
+  DEFAULT.take_format_errors()
+}
+
+// ---------------------------------------------------------------------------
+// Loaded-language enumeration
+// ---------------------------------------------------------------------------
+
 pub fn languages() -> Vec<String> {
-  vendor::classic::languages()
+  DEFAULT.languages()
 }
 
 // ---------------------------------------------------------------------------
 // Translation loading — from HashMap
 // ---------------------------------------------------------------------------
 
-/// Load translations from a plain key-value map.
+/// Load translations from a plain key-value map into the classic catalogs.
 ///
-/// Only available in `classic` mode. In `fluent` mode this function returns
-/// an error because a flat `HashMap` cannot represent Fluent syntax; use
+/// Requires the `classic` feature. Without it this function returns an error
+/// because a flat `HashMap` cannot represent Fluent syntax; use
 /// [`load_translations_from_text`] with raw `.ftl` content instead.
-#[cfg(not(feature = "fluent"))]
 pub fn load_translations_from_map(
   language: impl AsRef<str>,
   translations: HashMap<String, String>,
-) -> Result<(), String> {
-  vendor::classic::load_translations_from_map(language, translations);
-  Ok(())
+) -> Result<(), I18nError> {
+  DEFAULT.load_translations_from_map(language, translations)
 }
 
-#[cfg(feature = "fluent")]
-pub fn load_translations_from_map(
-  _language: impl AsRef<str>,
-  _translations: HashMap<String, String>,
-) -> Result<(), String> {
-  Err(
-    "load_translations_from_map is not supported in fluent mode; \
-     use load_translations_from_text with raw .ftl content instead"
-      .to_string(),
-  )
+// ---------------------------------------------------------------------------
+// Translation loading — from text
+// ---------------------------------------------------------------------------
+
+/// Load `content` in the default [`Format`]: Fluent when the `fluent` feature
+/// is enabled, classic otherwise.
+pub fn load_translations_from_text(
+  language: impl AsRef<str>,
+  content: impl AsRef<str>,
+) -> Result<(), I18nError> {
+  DEFAULT.load_translations_from_text(language, content)
+}
+
+/// Load `content` into the backend for `format`, which is how `.egl` and
+/// `.ftl` catalogs are mixed when both features are enabled.
+pub fn load_translations_from_text_with_format(
+  language: impl AsRef<str>,
+  content: impl AsRef<str>,
+  format: Format,
+) -> Result<(), I18nError> {
+  DEFAULT.load_translations_from_text_with_format(language, content, format)
 }
 
 // ---------------------------------------------------------------------------
-// Translation loading — from text
+// Translation loading — Fluent resources
 // ---------------------------------------------------------------------------
 
+/// Add a Fluent resource to `language` without dropping its other resources.
+/// See [`I18n::add_resource`].
 #[cfg(feature = "fluent")]
-pub fn load_translations_from_text(
+pub fn add_resource(
   language: impl AsRef<str>,
+  id: impl AsRef<str>,
   content: impl AsRef<str>,
-) -> Result<(), String> {
-  vendor::fluent::load_translations_from_text(
-    language.as_ref(),
-    content.as_ref(),
-    get_use_isolating(),
-  )
+) -> Result<(), I18nError> {
+  DEFAULT.add_resource(language, id, content)
 }
 
-#[cfg(not(feature = "fluent"))]
-pub fn load_translations_from_text(
+/// See [`I18n::add_resource_overriding`].
+#[cfg(feature = "fluent")]
+pub fn add_resource_overriding(
   language: impl AsRef<str>,
+  id: impl AsRef<str>,
   content: impl AsRef<str>,
-) -> Result<(), String> {
-  vendor::classic::load_translations_from_text(language, content)
+) -> Result<Vec<Override>, I18nError> {
+  DEFAULT.add_resource_overriding(language, id, content)
+}
+
+#[cfg(feature = "fluent")]
+pub fn remove_resource(language: impl AsRef<str>, id: impl AsRef<str>) -> bool {
+  DEFAULT.remove_resource(language, id)
+}
+
+#[cfg(feature = "fluent")]
+pub fn resources(language: impl AsRef<str>) -> Vec<String> {
+  DEFAULT.resources(language)
+}
+
+// ---------------------------------------------------------------------------
+// Fluent functions
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+pub fn add_function<F>(name: impl AsRef<str>, function: F)
+where
+  F: for<'a> Fn(&[fluent::FluentValue<'a>], &fluent::FluentArgs) -> fluent::FluentValue<'a>
+    + Send
+    + Sync
+    + 'static,
+{
+  DEFAULT.add_function(name, function)
 }
 
 // ---------------------------------------------------------------------------
@@ -147,101 +289,256 @@ pub fn load_translations_from_text(
 // ---------------------------------------------------------------------------
 
 /// Load all `.egl` / `.ftl` translation files from a directory (or a single
-/// file). Each file's stem is used as the language identifier.
-pub fn load_translations_from_path(path: impl AsRef<str>) -> Result<(), String> {
-  let path_ref = Path::new(path.as_ref());
-  let mut files = vec![];
-
-  if path_ref.is_file() {
-    files.push(path_ref.to_path_buf());
-  } else {
-    let read_dir = match fs::read_dir(path_ref) {
-      Ok(v) => v,
-      Err(e) => return Err(format!("{:?}", e)),
-    };
-    for entry in read_dir {
-      let path_file = match entry {
-        Ok(dir_entry) => dir_entry.path(),
-        Err(e) => {
-          log::warn!("failed to read directory entry: {:?}", e);
-          continue;
-        },
-      };
-      let allowed = path_file
-        .extension()
-        .map(|ext| {
-          let ext = ext.to_string_lossy().to_lowercase();
-          ext == "egl" || ext == "ftl"
-        })
-        .unwrap_or(false);
-      if !allowed {
-        continue;
-      }
-      files.push(path_file);
-    }
-  }
-
-  for file in files {
-    let name = match file.file_stem() {
-      Some(v) => v.to_string_lossy().to_string(),
-      None => continue,
-    };
-    match fs::read_to_string(&file) {
-      Ok(content) => load_translations_from_text(name, content)?,
-      Err(e) => return Err(format!("{:?}", e)),
-    }
-  }
-  Ok(())
+/// file). Each file's stem is used as the language identifier, and its
+/// extension picks the backend. A file that fails to load does not stop the
+/// others; the first failure is returned.
+pub fn load_translations_from_path(path: impl AsRef<str>) -> Result<(), I18nError> {
+  DEFAULT.load_translations_from_path(path)
+}
+
+/// Load the `.egl` / `.ftl` files under a directory, recursing as `layout`
+/// says: [`Layout::LanguageDirs`] for `locales/en-US/main.ftl`,
+/// [`Layout::DomainDirs`] for `i18n/plugin-x/en-US.ftl` in domain
+/// `plugin-x`. The files of one language are merged.
+pub fn load_translations_from_path_with_layout(
+  path: impl AsRef<std::path::Path>,
+  layout: Layout,
+) -> Result<(), I18nError> {
+  DEFAULT.load_translations_from_path_with_layout(path, layout)
+}
+
+/// Load the files at `path` like [`load_translations_from_path`] and report
+/// each one; see [`load_from_source_with_report`].
+pub fn load_translations_from_path_with_report(
+  path: impl AsRef<std::path::Path>,
+  mode: LoadMode,
+) -> LoadReport {
+  DEFAULT.load_translations_from_path_with_report(path, mode)
+}
+
+/// Load the translations at `path` like [`load_translations_from_path`] and
+/// reload them when they change; see [`Watcher`].
+#[cfg(feature = "hot-reload")]
+pub fn watch_path(path: impl AsRef<std::path::Path>) -> Result<Watcher, I18nError> {
+  DEFAULT.watch_path(path)
+}
+
+// ---------------------------------------------------------------------------
+// Translation loading — embedded at build time
+// ---------------------------------------------------------------------------
+
+/// Load translation files embedded with
+/// `egui_i18n_macros::include_translations!`, named and routed like files
+/// loaded from a path. Needs no filesystem, so it also works on wasm.
+pub fn load_embedded(embedded: &Embedded) -> Result<(), I18nError> {
+  DEFAULT.load_embedded(embedded)
+}
+
+// ---------------------------------------------------------------------------
+// Translation loading — from any source
+// ---------------------------------------------------------------------------
+
+/// Load every entry of `source` into the backend its format picks, in the
+/// order the source lists them. An entry that fails to load does not stop
+/// the others; the first failure is returned and the rest are logged.
+/// Entries that name a [domain](SourceEntry::domain) load into it.
+///
+/// Directories ([`FileSource`]), files in memory ([`MemorySource`]) and
+/// embedded files ([`Embedded`]) are built in; implement
+/// [`TranslationSource`] for other packaging.
+pub fn load_from_source(source: &impl TranslationSource) -> Result<(), I18nError> {
+  DEFAULT.load_from_source(source)
+}
+
+/// Load `source` like [`load_from_source`] and list which entries loaded and
+/// why the others failed. With [`LoadMode::Strict`], nothing is loaded
+/// unless every entry would load: the entries are tried on a copy of the
+/// catalogs first.
+///
+/// ```ignore
+/// let report = egui_i18n::load_from_source_with_report(&source, LoadMode::Strict);
+/// for error in &report.failed {
+///   eprintln!("{}", error);
+/// }
+/// ```
+pub fn load_from_source_with_report(source: &impl TranslationSource, mode: LoadMode) -> LoadReport {
+  DEFAULT.load_from_source_with_report(source, mode)
+}
+
+// ---------------------------------------------------------------------------
+// Domains
+// ---------------------------------------------------------------------------
+
+/// Load `source` into `domain`, which has its own catalogs, e.g. those a
+/// plugin ships. See [`I18n::load_domain_from_source`].
+pub fn load_domain_from_source(
+  domain: &str,
+  source: &impl TranslationSource,
+) -> Result<(), I18nError> {
+  DEFAULT.load_domain_from_source(domain, source)
+}
+
+pub fn load_domain_from_source_with_report(
+  domain: &str,
+  source: &impl TranslationSource,
+  mode: LoadMode,
+) -> LoadReport {
+  DEFAULT.load_domain_from_source_with_report(domain, source, mode)
+}
+
+pub fn load_domain_translations_from_text(
+  domain: impl AsRef<str>,
+  language: impl AsRef<str>,
+  content: impl AsRef<str>,
+) -> Result<(), I18nError> {
+  DEFAULT.load_domain_translations_from_text(domain, language, content)
+}
+
+pub fn unload_domain(domain: &str) -> bool {
+  DEFAULT.unload_domain(domain)
+}
+
+pub fn domains() -> Vec<String> {
+  DEFAULT.domains()
+}
+
+pub fn domain_languages(domain: &str) -> Vec<String> {
+  DEFAULT.domain_languages(domain)
 }
 
 // ---------------------------------------------------------------------------
 // Translation execution
 // ---------------------------------------------------------------------------
 
-#[cfg(not(feature = "fluent"))]
+/// Translate `key` through every compiled-in backend; this is what `tr!`
+/// expands to. See [`I18n::translate`] for the lookup order.
+pub fn translate(key: &str, args: &Args) -> String {
+  DEFAULT.translate(key, args)
+}
+
+/// Translate `key` in message context `context`; this is what
+/// `tr!(ctx: …, key)` expands to. See [`I18n::translate_ctx`].
+pub fn translate_ctx(context: &str, key: &str, args: &Args) -> String {
+  DEFAULT.translate_ctx(context, key, args)
+}
+
+/// Translate `key` from `domain`, falling back to the core domain; this is
+/// what `tr!(domain = …, key)` expands to. See [`I18n::translate_in`].
+pub fn translate_in(domain: &str, key: &str, args: &Args) -> String {
+  DEFAULT.translate_in(domain, key, args)
+}
+
+/// Like [`translate`], but shares the result with the translation cache. See
+/// [`I18n::translate_shared`].
+pub fn translate_shared(key: &str, args: &Args) -> std::sync::Arc<str> {
+  DEFAULT.translate_shared(key, args)
+}
+
+/// Like [`translate`], but returns the Fluent formatting errors instead of
+/// passing them to the [hook](set_format_error_hook).
+#[cfg(feature = "fluent")]
+pub fn try_translate(key: &str, args: &Args) -> (String, Vec<crate::fluent::FluentError>) {
+  DEFAULT.try_translate(key, args)
+}
+
+/// The attribute names of Fluent message `key`. See
+/// [`I18n::message_attributes`].
+#[cfg(feature = "fluent")]
+pub fn message_attributes(key: &str) -> Vec<String> {
+  DEFAULT.message_attributes(key)
+}
+
+#[cfg(feature = "classic")]
 pub fn translate_classic(key: &str, args: &HashMap<&str, String>) -> String {
-  let language = get_language();
-  let fallback = get_fallback();
-  vendor::classic::translate(language, fallback, key, args)
+  DEFAULT.translate_classic(key, args)
 }
 
 #[cfg(feature = "fluent")]
 pub fn translate_fluent(key: &str, args: &crate::fluent::FluentArgs) -> String {
-  let language = get_language();
-  let fallback = get_fallback();
-  vendor::fluent::translate(language, fallback, key, args)
+  DEFAULT.translate_fluent(key, args)
+}
+
+// ---------------------------------------------------------------------------
+// Translation cache
+// ---------------------------------------------------------------------------
+
+/// Memoize `tr!` results by key and arguments. See
+/// [`I18n::set_cache_enabled`].
+pub fn set_cache_enabled(enabled: bool) {
+  DEFAULT.set_cache_enabled(enabled);
+}
+
+pub fn is_cache_enabled() -> bool {
+  DEFAULT.is_cache_enabled()
+}
+
+pub fn cache_stats() -> CacheStats {
+  DEFAULT.cache_stats()
+}
+
+pub fn clear_cache() {
+  DEFAULT.clear_cache();
 }
 
 // ---------------------------------------------------------------------------
 // tr! macro
 // ---------------------------------------------------------------------------
 
-#[cfg(not(feature = "fluent"))]
 #[macro_export]
 macro_rules! tr {
-  ($key:expr, {$($name:ident: $val:expr),*}) => {{
-    let mut args = std::collections::HashMap::new();
+  (domain = $domain:expr, ctx: $ctx:expr, $key:expr, {$($name:ident: $val:expr),*}) => {{
+    let mut args = $crate::Args::new();
     $(
-      args.insert(stringify!($name), $val.to_string());
+      args.set(stringify!($name), $val);
     )*
-    $crate::translate_classic($key, &args)
+    $crate::translate_in($domain, &$crate::context_key($ctx, $key), &args)
   }};
-  ($key:expr) => {{
-    $crate::translate_classic($key, &std::collections::HashMap::new())
+  (domain = $domain:expr, ctx: $ctx:expr, $key:expr) => {{
+    $crate::translate_in($domain, &$crate::context_key($ctx, $key), &$crate::Args::new())
+  }};
+  (ctx: $ctx:expr, $key:expr, {$($name:ident: $val:expr),*}) => {{
+    let mut args = $crate::Args::new();
+    $(
+      args.set(stringify!($name), $val);
+    )*
+    $crate::translate_ctx($ctx, $key, &args)
+  }};
+  (ctx: $ctx:expr, $key:expr) => {{
+    $crate::translate_ctx($ctx, $key, &$crate::Args::new())
+  }};
+  (domain = $domain:expr, $key:expr, {$($name:ident: $val:expr),*}) => {{
+    let mut args = $crate::Args::new();
+    $(
+      args.set(stringify!($name), $val);
+    )*
+    $crate::translate_in($domain, $key, &args)
+  }};
+  (domain = $domain:expr, $key:expr) => {{
+    $crate::translate_in($domain, $key, &$crate::Args::new())
   }};
-}
-
-#[cfg(feature = "fluent")]
-#[macro_export]
-macro_rules! tr {
   ($key:expr, {$($name:ident: $val:expr),*}) => {{
-    let mut args = $crate::fluent::FluentArgs::new();
+    let mut args = $crate::Args::new();
     $(
       args.set(stringify!($name), $val);
     )*
-    $crate::translate_fluent($key, &args)
+    $crate::translate($key, &args)
   }};
   ($key:expr) => {{
-    $crate::translate_fluent($key, &$crate::fluent::FluentArgs::new())
+    $crate::translate($key, &$crate::Args::new())
+  }};
+}
+
+/// Translate attribute `attr` of message `msg`, i.e. `tr!("msg.attr")`.
+///
+/// ```ignore
+/// ui.button(tr!("login-button")).on_hover_text(tr_attr!("login-button", "tooltip"));
+/// ```
+#[macro_export]
+macro_rules! tr_attr {
+  ($msg:expr, $attr:expr, {$($name:ident: $val:expr),*}) => {{
+    $crate::tr!(&::std::format!("{}.{}", $msg, $attr), {$($name: $val),*})
+  }};
+  ($msg:expr, $attr:expr) => {{
+    $crate::tr!(&::std::format!("{}.{}", $msg, $attr))
   }};
 }
@@ -0,0 +1,127 @@
+//! BCP-47 language tag helpers used for locale negotiation.
+
+use unic_langid::LanguageIdentifier;
+
+/// Canonical form of a language tag: `en_us`, `en_US` and `en-US` all become
+/// `en-US`, and `zh_hant_tw` becomes `zh-Hant-TW`.
+///
+/// Tags that are not well-formed BCP-47 are returned unchanged, so catalogs
+/// loaded under arbitrary names still match themselves exactly.
+pub fn normalize(tag: &str) -> String {
+  match tag.parse::<LanguageIdentifier>() {
+    Ok(id) => id.to_string(),
+    Err(_) => tag.to_string(),
+  }
+}
+
+/// The tags tried for `tag`, from most to least specific: variants are
+/// dropped first, then the region, then the script.
+///
+/// `zh-Hant-TW` expands to `["zh-Hant-TW", "zh-Hant", "zh"]`. Tags that are
+/// not well-formed BCP-47 expand to themselves only; an empty tag expands to
+/// nothing.
+pub fn expand(tag: &str) -> Vec<String> {
+  if tag.is_empty() {
+    return vec![];
+  }
+  let mut id = match tag.parse::<LanguageIdentifier>() {
+    Ok(v) => v,
+    Err(_) => return vec![tag.to_string()],
+  };
+
+  let mut chain = vec![id.to_string()];
+  let mut push = |id: &LanguageIdentifier| {
+    let tag = id.to_string();
+    if !chain.contains(&tag) {
+      chain.push(tag);
+    }
+  };
+  id.clear_variants();
+  push(&id);
+  id.region = None;
+  push(&id);
+  id.script = None;
+  push(&id);
+  chain
+}
+
+/// The full negotiated chain: `language` expanded with [`expand`], followed by
+/// each entry of `fallbacks` expanded the same way, without duplicates.
+///
+/// ```
+/// let chain = egui_i18n::locale::fallback_chain("zh-Hant-TW", &["en-US"]);
+/// assert_eq!(chain, ["zh-Hant-TW", "zh-Hant", "zh", "en-US", "en"]);
+/// ```
+pub fn fallback_chain(language: &str, fallbacks: &[impl AsRef<str>]) -> Vec<String> {
+  let mut chain: Vec<String> = vec![];
+  let tags = std::iter::once(language).chain(fallbacks.iter().map(AsRef::as_ref));
+  for tag in tags {
+    for expanded in expand(tag) {
+      if !chain.contains(&expanded) {
+        chain.push(expanded);
+      }
+    }
+  }
+  chain
+}
+
+/// Order the `available` catalog names by how well they match the
+/// `requested` languages (most preferred first), dropping those that match
+/// none of them.
+///
+/// For each requested tag, in order, the following are collected: an exact
+/// match, catalogs that are more specific than it (`en` → `en-US`), its less
+/// specific forms (`en-GB` → `en`), and finally any catalog of the same base
+/// language (`en-GB` → `en-US`). Comparisons use [`normalize`]d tags, and the
+/// returned names are the `available` entries as given.
+///
+/// ```
+/// let matched = egui_i18n::locale::negotiate(&["fr-CA", "en"], &["en-US", "fr", "de"]);
+/// assert_eq!(matched, ["fr", "en-US"]);
+/// ```
+pub fn negotiate(requested: &[impl AsRef<str>], available: &[impl AsRef<str>]) -> Vec<String> {
+  let available: Vec<(&str, Vec<String>)> =
+    available.iter().map(|tag| (tag.as_ref(), expand(tag.as_ref()))).collect();
+  let mut matched: Vec<String> = vec![];
+  let mut push = |name: &str| {
+    if !matched.iter().any(|v| v == name) {
+      matched.push(name.to_string());
+    }
+  };
+
+  for requested in requested {
+    let chain = expand(requested.as_ref());
+    let Some(wanted) = chain.first() else {
+      continue;
+    };
+    // Exact match.
+    for (name, expanded) in &available {
+      if expanded.first() == Some(wanted) {
+        push(name);
+      }
+    }
+    // More specific catalogs: `wanted` is one of their less specific forms.
+    for (name, expanded) in &available {
+      if expanded.iter().skip(1).any(|tag| tag == wanted) {
+        push(name);
+      }
+    }
+    // Less specific forms of the requested tag.
+    for tag in chain.iter().skip(1) {
+      for (name, expanded) in &available {
+        if expanded.first() == Some(tag) {
+          push(name);
+        }
+      }
+    }
+    // Same base language, any script or region.
+    if let Some(base) = chain.last() {
+      for (name, expanded) in &available {
+        if expanded.last() == Some(base) {
+          push(name);
+        }
+      }
+    }
+  }
+  matched
+}
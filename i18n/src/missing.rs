@@ -0,0 +1,137 @@
+//! What lookups return for keys no catalog has, and the record of such keys.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+/// A key that was not found in the active language nor in any fallback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingKey {
+  /// The key, or the [`context_key`](crate::context_key) of a key looked up
+  /// in a message context.
+  pub key: String,
+  /// The domain the key was looked up in, empty for the core domain.
+  pub domain: String,
+  /// The active language at the time of the lookup.
+  pub language: String,
+  /// The language chain that was searched, see
+  /// [`I18n::language_chain`](crate::I18n::language_chain).
+  pub chain: Vec<String>,
+}
+
+/// What a lookup returns for a missing key.
+#[derive(Clone, Default)]
+pub enum MissingKeyPolicy {
+  /// An empty string, so nothing is shown.
+  #[default]
+  Empty,
+  /// The key itself, without its message context.
+  Key,
+  /// The key wrapped in a marker, `⟦key⟧`, so it stands out in the UI.
+  Marker,
+  /// Whatever the callback returns.
+  Custom(Arc<dyn Fn(&MissingKey) -> String + Send + Sync>),
+}
+
+impl MissingKeyPolicy {
+  pub fn custom(handler: impl Fn(&MissingKey) -> String + Send + Sync + 'static) -> Self {
+    Self::Custom(Arc::new(handler))
+  }
+
+  pub(crate) fn resolve(&self, missing: &MissingKey) -> String {
+    let key = crate::split_context_key(&missing.key).map_or(missing.key.as_str(), |(_, key)| key);
+    match self {
+      Self::Empty => String::default(),
+      Self::Key => key.to_string(),
+      Self::Marker => format!("⟦{}⟧", key),
+      Self::Custom(handler) => handler(missing),
+    }
+  }
+}
+
+impl fmt::Debug for MissingKeyPolicy {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Empty => f.write_str("Empty"),
+      Self::Key => f.write_str("Key"),
+      Self::Marker => f.write_str("Marker"),
+      Self::Custom(_) => f.write_str("Custom(..)"),
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Process-wide record
+// ---------------------------------------------------------------------------
+
+/// Missing keys by (language, domain, key), with the chain of the most
+/// recent lookup.
+type Record = BTreeMap<(String, String, String), Vec<String>>;
+
+/// Every missing key seen by any translator.
+static RECORD: Lazy<Mutex<Record>> = Lazy::new(Default::default);
+
+pub(crate) fn record(missing: &MissingKey) {
+  let entry = (missing.language.clone(), missing.domain.clone(), missing.key.clone());
+  RECORD.lock().unwrap().insert(entry, missing.chain.clone());
+}
+
+/// Every key that was missing at runtime, across all translators, sorted by
+/// language, domain and key. Each appears once.
+pub fn missing_keys() -> Vec<MissingKey> {
+  let record = RECORD.lock().unwrap();
+  record
+    .iter()
+    .map(|((language, domain, key), chain)| MissingKey {
+      key: key.clone(),
+      domain: domain.clone(),
+      language: language.clone(),
+      chain: chain.clone(),
+    })
+    .collect()
+}
+
+pub fn clear_missing_keys() {
+  RECORD.lock().unwrap().clear();
+}
+
+/// Write the [`missing_keys`] to `path`, grouped by language and domain, as
+/// a skeleton catalog for translators:
+///
+/// ```text
+/// # de-DE
+/// settings-title =
+///
+/// # de-DE (plugin-x)
+/// export =
+/// ```
+///
+/// With the `classic` feature, keys are written with
+/// [`escape_key`](crate::escape_key), so
+/// [`parse_translations`](crate::parse_translations) reads them back.
+pub fn dump_missing_keys(path: impl AsRef<Path>) -> io::Result<()> {
+  let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+  let mut current: Option<(String, String)> = None;
+  for missing in missing_keys() {
+    let group = (missing.language.clone(), missing.domain.clone());
+    if current.as_ref() != Some(&group) {
+      if current.is_some() {
+        writeln!(file)?;
+      }
+      match missing.domain.as_str() {
+        "" => writeln!(file, "# {}", missing.language)?,
+        domain => writeln!(file, "# {} ({})", missing.language, domain)?,
+      }
+      current = Some(group);
+    }
+    #[cfg(feature = "classic")]
+    writeln!(file, "{} =", crate::escape_key(&missing.key))?;
+    #[cfg(not(feature = "classic"))]
+    writeln!(file, "{} =", missing.key)?;
+  }
+  file.flush()
+}
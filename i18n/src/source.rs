@@ -0,0 +1,406 @@
+//! Where translation files come from.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{Embedded, Format, I18nError};
+
+/// One translation file of a [`TranslationSource`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceEntry<'a> {
+  /// Names the entry in errors and is its Fluent resource id, so entries for
+  /// the same language add up. Files use their path.
+  pub id: Cow<'a, str>,
+  pub language: Cow<'a, str>,
+  /// The domain the entry loads into, below the one the source is loaded
+  /// into; empty for that domain itself. [`Layout::DomainDirs`] sets it to
+  /// the file's directory.
+  pub domain: Cow<'a, str>,
+  /// Picks the backend; a format whose backend is not compiled in goes to
+  /// the default one.
+  pub format: Format,
+  /// UTF-8 text.
+  pub bytes: Cow<'a, [u8]>,
+}
+
+impl<'a> SourceEntry<'a> {
+  /// An entry named after the stem of `path` and routed by its extension, as
+  /// [`load_translations_from_path`](crate::load_translations_from_path)
+  /// names files. `None` when the extension is not `.egl` or `.ftl`.
+  pub fn from_path(path: impl Into<Cow<'a, str>>, bytes: impl Into<Cow<'a, [u8]>>) -> Option<Self> {
+    let id = path.into();
+    let (language, format) = {
+      let path = Path::new(id.as_ref());
+      let format = Format::from_extension(path.extension()?.to_string_lossy())?;
+      (path.file_stem()?.to_string_lossy().to_string(), format)
+    };
+    let language = Cow::Owned(language);
+    Some(Self { id, language, domain: Cow::Borrowed(""), format, bytes: bytes.into() })
+  }
+
+  /// The bytes as text; invalid UTF-8 is an [`I18nError::Io`] error with
+  /// [`io::ErrorKind::InvalidData`], as when reading a file.
+  pub fn text(&self) -> Result<&str, I18nError> {
+    std::str::from_utf8(&self.bytes).map_err(|error| I18nError::Io {
+      path: PathBuf::from(self.id.as_ref()),
+      source: io::Error::new(io::ErrorKind::InvalidData, error),
+    })
+  }
+}
+
+/// A set of translation files to load with
+/// [`load_from_source`](crate::load_from_source): a directory, files in
+/// memory, files embedded at build time, or anything an app packages its
+/// translations in (an archive, an asset bundle, a download).
+///
+/// ```ignore
+/// struct Archive(zip::ZipArchive<std::fs::File>);
+///
+/// impl TranslationSource for Archive {
+///   fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+///     // One `SourceEntry::from_path(name, bytes)` per file.
+///   }
+/// }
+/// ```
+pub trait TranslationSource {
+  /// Every file, in the order to load them.
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError>;
+
+  /// The files that could be read, and an error for each one that could
+  /// not, so a load can go on without them. Defaults to
+  /// [`entries`](Self::entries), which fails as a whole.
+  fn entries_with_errors(&self) -> (Vec<SourceEntry<'_>>, Vec<I18nError>) {
+    match self.entries() {
+      Ok(entries) => (entries, vec![]),
+      Err(error) => (vec![], vec![error]),
+    }
+  }
+}
+
+impl<S: TranslationSource + ?Sized> TranslationSource for &S {
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    (**self).entries()
+  }
+
+  fn entries_with_errors(&self) -> (Vec<SourceEntry<'_>>, Vec<I18nError>) {
+    (**self).entries_with_errors()
+  }
+}
+
+/// What to do when some entries of a source fail to load.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LoadMode {
+  /// Load every entry that can be loaded and report the others.
+  #[default]
+  Lenient,
+  /// Load nothing unless every entry can be read and parsed and fits with
+  /// the catalogs already loaded.
+  Strict,
+}
+
+/// What a load did, entry by entry. Returned by
+/// [`load_from_source_with_report`](crate::load_from_source_with_report).
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[must_use]
+pub struct LoadReport {
+  /// The entries that loaded, by id (the path, for files), in load order.
+  pub loaded: Vec<PathBuf>,
+  /// Why the others failed, each error naming its file where known, and the
+  /// directories that could not be read.
+  pub failed: Vec<I18nError>,
+}
+
+impl LoadReport {
+  /// Whether nothing failed.
+  pub fn is_ok(&self) -> bool {
+    self.failed.is_empty()
+  }
+
+  /// The first failure, if any.
+  pub fn into_result(self) -> Result<(), I18nError> {
+    match self.failed.into_iter().next() {
+      Some(error) => Err(error),
+      None => Ok(()),
+    }
+  }
+}
+
+/// How the translation files under a directory map to languages.
+///
+/// Files directly in the directory are named after their stem in every
+/// layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Layout {
+  /// `<lang>.<ext>` directly in the directory, e.g. `i18n/en-US.ftl`.
+  /// Subdirectories are not read.
+  #[default]
+  Flat,
+  /// `<lang>/<any>.<ext>`: a directory per language holding any number of
+  /// files, at any depth, e.g. `locales/en-US/main.ftl` and
+  /// `locales/en-US/settings/advanced.ftl`, the Fluent and Pontoon
+  /// convention.
+  LanguageDirs,
+  /// `<domain>/<lang>.<ext>`: files named after their language in a
+  /// directory per [domain](crate::I18n::load_domain_from_source), at any
+  /// depth, e.g. `i18n/plugin-x/en-US.ftl` for domain `plugin-x` and
+  /// `i18n/plugins/export/en-US.ftl` for domain `plugins/export`. Files
+  /// directly in the directory go to the core domain.
+  DomainDirs,
+}
+
+/// The `.egl` and `.ftl` files under a directory, found by its [`Layout`], or
+/// a single file, as loaded by
+/// [`load_translations_from_path`](crate::load_translations_from_path).
+///
+/// Files are listed sorted by path. Each is its own resource, so the files of
+/// one language add up; where two define the same classic key, the later
+/// one wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSource {
+  path: PathBuf,
+  layout: Layout,
+}
+
+impl FileSource {
+  pub fn new(path: impl Into<PathBuf>) -> Self {
+    Self { path: path.into(), layout: Layout::default() }
+  }
+
+  /// Find the files by `layout` instead of [`Layout::Flat`].
+  pub fn layout(mut self, layout: Layout) -> Self {
+    self.layout = layout;
+    self
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// The files with their domains and languages, sorted by path, and the
+  /// errors for the subdirectories that could not be read. Fails when the
+  /// directory itself cannot be read.
+  pub(crate) fn files(&self) -> Result<(Files, Vec<I18nError>), I18nError> {
+    let path = &self.path;
+    if path.is_file() {
+      // Named explicitly, so loaded whatever its extension.
+      let language = path.file_stem().map(|stem| stem.to_string_lossy().to_string());
+      let file = language.map(|language| (path.clone(), String::new(), language));
+      return Ok((file.into_iter().collect(), vec![]));
+    }
+    let mut files = vec![];
+    let mut errors = vec![];
+    walk(path, None, "", self.layout, &mut files, &mut errors)?;
+    files.sort();
+    Ok((files, errors))
+  }
+
+  /// The entry for `file`, routed by its extension or, without a known one,
+  /// to the default format.
+  pub(crate) fn entry(
+    file: &Path,
+    domain: &str,
+    language: &str,
+  ) -> Result<SourceEntry<'static>, I18nError> {
+    let format = file
+      .extension()
+      .and_then(|ext| Format::from_extension(ext.to_string_lossy()))
+      .unwrap_or_default();
+    match fs::read(file) {
+      Ok(bytes) => Ok(SourceEntry {
+        id: Cow::Owned(file.to_string_lossy().to_string()),
+        language: Cow::Owned(language.to_string()),
+        domain: Cow::Owned(domain.to_string()),
+        format,
+        bytes: Cow::Owned(bytes),
+      }),
+      Err(source) => Err(I18nError::Io { path: file.to_path_buf(), source }),
+    }
+  }
+}
+
+impl TranslationSource for FileSource {
+  /// Fails on the first file or directory that cannot be read.
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    let (entries, errors) = self.entries_with_errors();
+    match errors.into_iter().next() {
+      Some(error) => Err(error),
+      None => Ok(entries),
+    }
+  }
+
+  fn entries_with_errors(&self) -> (Vec<SourceEntry<'_>>, Vec<I18nError>) {
+    let (files, mut errors) = match self.files() {
+      Ok(v) => v,
+      Err(error) => return (vec![], vec![error]),
+    };
+    let mut entries = vec![];
+    for (file, domain, language) in files {
+      match Self::entry(&file, &domain, &language) {
+        Ok(entry) => entries.push(entry),
+        Err(error) => errors.push(error),
+      }
+    }
+    (entries, errors)
+  }
+}
+
+/// Translation files, with their domains and languages.
+pub(crate) type Files = Vec<(PathBuf, String, String)>;
+
+/// `child` below domain `parent`, e.g. `plugins/export`.
+pub(crate) fn subdomain(parent: &str, child: &str) -> String {
+  match (parent, child) {
+    ("", child) => child.to_string(),
+    (parent, "") => parent.to_string(),
+    (parent, child) => format!("{}/{}", parent, child),
+  }
+}
+
+/// Collect the translation files in `dir` into `files`, and the errors for
+/// unreadable entries and subdirectories into `errors`. `language` is the
+/// language directory `dir` is in, for [`Layout::LanguageDirs`], and
+/// `domain` the domain it holds, for [`Layout::DomainDirs`].
+fn walk(
+  dir: &Path,
+  language: Option<&str>,
+  domain: &str,
+  layout: Layout,
+  files: &mut Files,
+  errors: &mut Vec<I18nError>,
+) -> Result<(), I18nError> {
+  let read_dir = match fs::read_dir(dir) {
+    Ok(v) => v,
+    Err(source) => return Err(I18nError::Io { path: dir.to_path_buf(), source }),
+  };
+  for entry in read_dir {
+    let entry = match entry {
+      Ok(dir_entry) => dir_entry,
+      Err(source) => {
+        errors.push(I18nError::Io { path: dir.to_path_buf(), source });
+        continue;
+      },
+    };
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().to_string();
+    // Symlinked directories are not followed, so a link cannot loop.
+    if entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false) {
+      if name.starts_with('.') {
+        continue;
+      }
+      let (language, domain) = match layout {
+        Layout::Flat => continue,
+        Layout::LanguageDirs => (Some(language.unwrap_or(&name)), String::new()),
+        Layout::DomainDirs => (None, subdomain(domain, &name)),
+      };
+      if let Err(error) = walk(&path, language, &domain, layout, files, errors) {
+        errors.push(error);
+      }
+      continue;
+    }
+    let allowed = path
+      .extension()
+      .map(|ext| Format::from_extension(ext.to_string_lossy()).is_some())
+      .unwrap_or(false);
+    if !allowed || !path.is_file() {
+      continue;
+    }
+    let language = match language {
+      Some(language) => language.to_string(),
+      None => match path.file_stem() {
+        Some(stem) => stem.to_string_lossy().to_string(),
+        None => continue,
+      },
+    };
+    files.push((path, domain.to_string(), language));
+  }
+  Ok(())
+}
+
+/// Translation files held in memory, e.g. downloaded or unpacked by the app.
+///
+/// ```ignore
+/// let source = MemorySource::new()
+///   .add("en-US", Format::Fluent, en_us_bytes)
+///   .add_file("i18n/de-DE.egl", de_de_bytes);
+/// egui_i18n::load_from_source(&source)?;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemorySource {
+  entries: Vec<SourceEntry<'static>>,
+}
+
+impl MemorySource {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add `bytes` for `language`, identified as `<language>.<extension>`.
+  pub fn add(
+    mut self,
+    language: impl Into<String>,
+    format: Format,
+    bytes: impl Into<Vec<u8>>,
+  ) -> Self {
+    let language = language.into();
+    let id = format!("{}.{}", language, format.extension());
+    self.entries.push(SourceEntry {
+      id: Cow::Owned(id),
+      language: Cow::Owned(language),
+      domain: Cow::Borrowed(""),
+      format,
+      bytes: Cow::Owned(bytes.into()),
+    });
+    self
+  }
+
+  /// Add `bytes` named and routed by `path`, like a file in a directory. A
+  /// path whose extension is not `.egl` or `.ftl` is skipped, as
+  /// directories skip such files.
+  pub fn add_file(mut self, path: impl Into<String>, bytes: impl Into<Vec<u8>>) -> Self {
+    let path: String = path.into();
+    let bytes: Vec<u8> = bytes.into();
+    self.entries.extend(SourceEntry::from_path(path, bytes));
+    self
+  }
+}
+
+impl TranslationSource for MemorySource {
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    Ok(
+      self
+        .entries
+        .iter()
+        .map(|entry| SourceEntry {
+          id: Cow::Borrowed(entry.id.as_ref()),
+          language: Cow::Borrowed(entry.language.as_ref()),
+          domain: Cow::Borrowed(entry.domain.as_ref()),
+          format: entry.format,
+          bytes: Cow::Borrowed(entry.bytes.as_ref()),
+        })
+        .collect(),
+    )
+  }
+}
+
+impl TranslationSource for Embedded {
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    Ok(
+      self
+        .files()
+        .iter()
+        .map(|file| SourceEntry {
+          id: Cow::Borrowed(file.path),
+          language: Cow::Borrowed(file.language),
+          domain: Cow::Borrowed(""),
+          format: file.format,
+          bytes: Cow::Borrowed(file.content.as_bytes()),
+        })
+        .collect(),
+    )
+  }
+}
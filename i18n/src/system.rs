@@ -0,0 +1,98 @@
+//! Sources for the user's preferred UI languages.
+
+/// Somewhere the user's preferred UI languages can be read from, most
+/// preferred first.
+///
+/// [`EnvLanguages`] covers Linux and other Unix-like systems. Other platforms
+/// plug in their own implementation; on wasm, the browser's
+/// `navigator.languages` can be handed over through [`StaticLanguages`].
+pub trait LanguageSource {
+  fn preferred_languages(&self) -> Vec<String>;
+}
+
+impl<F: Fn() -> Vec<String>> LanguageSource for F {
+  fn preferred_languages(&self) -> Vec<String> {
+    self()
+  }
+}
+
+/// A fixed list of languages, e.g. `navigator.languages` on wasm or a value
+/// from the app's settings.
+#[derive(Clone, Debug, Default)]
+pub struct StaticLanguages(pub Vec<String>);
+
+impl StaticLanguages {
+  pub fn new(languages: impl IntoIterator<Item = impl Into<String>>) -> Self {
+    Self(languages.into_iter().map(Into::into).collect())
+  }
+}
+
+impl LanguageSource for StaticLanguages {
+  fn preferred_languages(&self) -> Vec<String> {
+    self.0.clone()
+  }
+}
+
+type EnvLookup = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Preferred languages from the POSIX locale environment.
+///
+/// Follows gettext's precedence: `LC_ALL`, then `LC_MESSAGES`, then `LANG`
+/// decide the locale; unless that locale is `C` / `POSIX`, the
+/// colon-separated `LANGUAGE` list takes priority over it. Codeset and
+/// modifier suffixes are stripped, so `en_US.UTF-8@euro` yields `en-US`.
+pub struct EnvLanguages {
+  lookup: EnvLookup,
+}
+
+impl Default for EnvLanguages {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl EnvLanguages {
+  /// Read the process environment.
+  pub fn new() -> Self {
+    Self::with_lookup(|name| std::env::var(name).ok())
+  }
+
+  /// Read variables through `lookup` instead of the process environment,
+  /// which lets tests inject values.
+  pub fn with_lookup(lookup: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+    Self { lookup: Box::new(lookup) }
+  }
+
+  fn var(&self, name: &str) -> Option<String> {
+    (self.lookup)(name).filter(|v| !v.is_empty())
+  }
+}
+
+impl LanguageSource for EnvLanguages {
+  fn preferred_languages(&self) -> Vec<String> {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter().find_map(|name| self.var(name));
+    let locale = locale.as_deref().and_then(parse_posix_locale);
+    // `LANGUAGE` is ignored when the locale is unset or `C`, as gettext does.
+    let Some(locale) = locale else {
+      return vec![];
+    };
+
+    let mut languages: Vec<String> = vec![];
+    if let Some(list) = self.var("LANGUAGE") {
+      languages.extend(list.split(':').filter_map(parse_posix_locale));
+    }
+    if !languages.contains(&locale) {
+      languages.push(locale);
+    }
+    languages
+  }
+}
+
+/// `en_US.UTF-8@euro` → `en-US`; `None` for `C`, `POSIX` and empty values.
+fn parse_posix_locale(value: &str) -> Option<String> {
+  let value = value.split(['.', '@']).next().unwrap_or_default().trim();
+  if value.is_empty() || value == "C" || value == "POSIX" {
+    return None;
+  }
+  Some(crate::locale::normalize(value))
+}
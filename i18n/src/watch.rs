@@ -0,0 +1,254 @@
+//! Reloading translation files while the app runs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use crate::source::Files;
+use crate::{FileSource, I18n, I18nError};
+
+/// What one [`Watcher::poll`] changed.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct Reload {
+  /// Files that were added or changed and are loaded now.
+  pub loaded: Vec<PathBuf>,
+  /// Files that disappeared and are unloaded.
+  pub removed: Vec<PathBuf>,
+  /// Files that changed but failed to load, and a directory that could not
+  /// be read. The catalogs loaded before stay in place.
+  pub errors: Vec<I18nError>,
+}
+
+impl Reload {
+  /// Whether nothing changed.
+  pub fn is_empty(&self) -> bool {
+    self.loaded.is_empty() && self.removed.is_empty() && self.errors.is_empty()
+  }
+}
+
+type Callback = Box<dyn Fn(&Reload) + Send + Sync>;
+
+/// Watches the translation files of a [`FileSource`] and
+/// reloads each one into its translator when it changes. Meant for
+/// development, so translators and designers see their edits without a
+/// restart.
+///
+/// Files are compared by modification time and size. Every reload goes
+/// through the same loaders as
+/// [`load_translations_from_path`](I18n::load_translations_from_path), which
+/// replace a catalog or Fluent resource in one step, so lookups see either
+/// the old or the new file. A file that fails to load leaves the previous
+/// catalog in place; the error is logged and reported in the [`Reload`].
+///
+/// Nothing happens until [`poll`](Self::poll) is called, or after
+/// [`spawn`](Self::spawn) on a background thread that stops when the
+/// watcher is dropped.
+///
+/// ```ignore
+/// let watcher = egui_i18n::watch_path("assets/languages")?
+///   .request_repaint(&cc.egui_ctx)
+///   .spawn(Duration::from_millis(500));
+/// ```
+///
+/// A [`FileSource`] with another layout is watched with [`new`](Self::new):
+///
+/// ```ignore
+/// let source = FileSource::new("locales").layout(Layout::LanguageDirs);
+/// let watcher = Watcher::new(&i18n, source)?;
+/// // Keep `watcher` alive, e.g. in the app struct.
+/// ```
+pub struct Watcher {
+  shared: Arc<Shared>,
+  thread: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+struct Shared {
+  i18n: I18n,
+  source: FileSource,
+  state: Mutex<State>,
+  callbacks: Mutex<Vec<Callback>>,
+}
+
+#[derive(Default)]
+struct State {
+  /// The domain, language and stamp of each file loaded.
+  files: HashMap<PathBuf, (String, String, Stamp)>,
+  /// The directory could not be read last time; reported once.
+  unreadable: bool,
+  /// The files that failed to load in [`Watcher::new`], reported by the
+  /// first poll.
+  pending: Vec<I18nError>,
+}
+
+type Stamp = (Option<SystemTime>, u64);
+
+fn stamp(file: &Path) -> Stamp {
+  match fs::metadata(file) {
+    Ok(metadata) => (metadata.modified().ok(), metadata.len()),
+    Err(_) => (None, 0),
+  }
+}
+
+impl Watcher {
+  /// Load the files of `source` into `i18n` and watch them. Only a
+  /// directory that cannot be read is an error; files that fail to load are
+  /// watched all the same and reported by the first [`poll`](Self::poll),
+  /// like a failed reload.
+  pub fn new(i18n: &I18n, source: FileSource) -> Result<Self, I18nError> {
+    let (found, errors) = source.files()?;
+    for error in errors {
+      log::warn!("{}", error);
+    }
+    let mut files = HashMap::new();
+    let mut pending = vec![];
+    for (file, domain, language) in found {
+      // Stamped before loading, so an edit during the load is seen next poll.
+      let stamp = stamp(&file);
+      if let Err(error) = i18n.load_file(&file, &domain, &language) {
+        pending.push(error);
+      }
+      files.insert(file, (domain, language, stamp));
+    }
+    let shared = Shared {
+      i18n: i18n.clone(),
+      source,
+      state: Mutex::new(State { files, unreadable: false, pending }),
+      callbacks: Mutex::default(),
+    };
+    Ok(Self { shared: Arc::new(shared), thread: None })
+  }
+
+  /// The watched directory or file.
+  pub fn path(&self) -> &Path {
+    self.shared.source.path()
+  }
+
+  /// Call `callback` after every poll that changed something.
+  pub fn on_reload(self, callback: impl Fn(&Reload) + Send + Sync + 'static) -> Self {
+    self.shared.callbacks.lock().unwrap().push(Box::new(callback));
+    self
+  }
+
+  /// Repaint `ctx` after every poll that changed something, so a running
+  /// app shows the new translations right away.
+  #[cfg(feature = "egui")]
+  pub fn request_repaint(self, ctx: &egui::Context) -> Self {
+    let ctx = ctx.clone();
+    self.on_reload(move |_| ctx.request_repaint())
+  }
+
+  /// Poll every `interval` on a background thread until the watcher is
+  /// dropped. Calling it again restarts the thread with the new interval.
+  pub fn spawn(mut self, interval: Duration) -> Self {
+    self.stop();
+    let (stop, stopped) = mpsc::channel::<()>();
+    let shared = self.shared.clone();
+    let thread = std::thread::Builder::new()
+      .name("egui-i18n-watcher".to_string())
+      .spawn(move || {
+        while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+          shared.poll();
+        }
+      })
+      .expect("failed to spawn the translation watcher thread");
+    self.thread = Some((stop, thread));
+    self
+  }
+
+  /// Reload the files that were added or changed since the last poll and
+  /// unload those that were removed.
+  pub fn poll(&self) -> Reload {
+    self.shared.poll()
+  }
+
+  fn stop(&mut self) {
+    if let Some((stop, thread)) = self.thread.take() {
+      drop(stop);
+      let _ = thread.join();
+    }
+  }
+}
+
+impl Drop for Watcher {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
+
+impl std::fmt::Debug for Watcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Watcher")
+      .field("source", &self.shared.source)
+      .field("spawned", &self.thread.is_some())
+      .finish()
+  }
+}
+
+impl Shared {
+  fn poll(&self) -> Reload {
+    let mut reload = Reload::default();
+    {
+      // Held throughout, so a background poll and a manual one never load
+      // the same change twice.
+      let mut state = self.state.lock().unwrap();
+      reload.errors.append(&mut state.pending);
+      match self.source.files() {
+        Ok((files, errors)) => {
+          // Unreadable subdirectories would be reported on every poll.
+          for error in errors {
+            log::warn!("{}", error);
+          }
+          state.unreadable = false;
+          self.reload(&mut state, files, &mut reload);
+        },
+        Err(error) => {
+          if !std::mem::replace(&mut state.unreadable, true) {
+            reload.errors.push(error);
+          }
+        },
+      }
+    }
+    for error in &reload.errors {
+      log::warn!("failed to reload translations: {}", error);
+    }
+    if !reload.is_empty() {
+      for callback in self.callbacks.lock().unwrap().iter() {
+        callback(&reload);
+      }
+    }
+    reload
+  }
+
+  fn reload(&self, state: &mut State, files: Files, reload: &mut Reload) {
+    let mut removed: Files = state
+      .files
+      .iter()
+      .filter(|(file, (_, language, _))| !files.iter().any(|(f, _, l)| f == *file && l == language))
+      .map(|(file, (domain, language, _))| (file.clone(), domain.clone(), language.clone()))
+      .collect();
+    removed.sort();
+    for (file, domain, language) in removed {
+      state.files.remove(&file);
+      self.i18n.unload_file(&file, &domain, &language);
+      reload.removed.push(file);
+    }
+    for (file, domain, language) in files {
+      let stamp = stamp(&file);
+      if state.files.get(&file).map(|(_, _, loaded)| loaded) == Some(&stamp) {
+        continue;
+      }
+      // Stamped even when loading fails, so the error is reported once per
+      // edit rather than on every poll.
+      state.files.insert(file.clone(), (domain.clone(), language.clone(), stamp));
+      match self.i18n.load_file(&file, &domain, &language) {
+        Ok(()) => reload.loaded.push(file),
+        Err(error) => reload.errors.push(error),
+      }
+    }
+  }
+}
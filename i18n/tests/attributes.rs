@@ -0,0 +1,115 @@
+//! Integration tests for Fluent message attributes and terms.
+#![cfg(feature = "fluent")]
+
+mod common;
+
+use egui_i18n::{tr, tr_attr, Args, Format, MissingKeyPolicy};
+
+const FTL: &str = r#"
+-brand = Acme
+    .gender = feminine
+
+login-button = Log in
+    .tooltip = Log in to { -brand }
+    .aria-label = Log in as { $user }
+
+only-attributes =
+    .title = Only a title
+
+about = { -brand.gender ->
+    [feminine] She is { -brand }
+   *[other] It is { -brand }
+}
+"#;
+
+fn args<'a>(pairs: &[(&'a str, &'a str)]) -> Args<'a> {
+  let mut args = Args::new();
+  for (name, value) in pairs {
+    args.set(name, *value);
+  }
+  args
+}
+
+// ---------------------------------------------------------------------------
+// Attributes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_value_and_attributes() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  assert_eq!(i18n.translate("login-button", &Args::new()), "Log in");
+  assert_eq!(i18n.translate("login-button.tooltip", &Args::new()), "Log in to Acme");
+  assert_eq!(i18n.translate("login-button.aria-label", &args(&[("user", "ada")])), "Log in as ada");
+}
+
+#[test]
+fn test_message_with_only_attributes() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(i18n.translate("only-attributes.title", &Args::new()), "Only a title");
+  // There is no value to show for the message itself.
+  assert_eq!(i18n.translate("only-attributes", &Args::new()), "only-attributes");
+}
+
+#[test]
+fn test_unknown_attribute_is_missing() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  i18n.set_missing_key_policy(MissingKeyPolicy::Marker);
+  assert_eq!(i18n.translate("login-button.nope", &Args::new()), "⟦login-button.nope⟧");
+}
+
+#[test]
+fn test_message_attributes() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  assert_eq!(i18n.message_attributes("login-button"), ["tooltip", "aria-label"]);
+  assert_eq!(i18n.message_attributes("only-attributes"), ["title"]);
+  assert!(i18n.message_attributes("about").is_empty());
+  assert!(i18n.message_attributes("nope").is_empty());
+}
+
+#[test]
+fn test_attributes_follow_the_fallback_chain() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  i18n.load_translations_from_text("de-DE", "login-button = Anmelden").unwrap();
+  i18n.set_language("de-DE");
+  i18n.set_fallback("en-US");
+  assert_eq!(i18n.translate("login-button", &Args::new()), "Anmelden");
+  assert_eq!(i18n.translate("login-button.tooltip", &Args::new()), "Log in to Acme");
+}
+
+// ---------------------------------------------------------------------------
+// Terms
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_term_lookup() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  assert_eq!(i18n.translate("-brand", &Args::new()), "Acme");
+  assert_eq!(i18n.translate("about", &Args::new()), "She is Acme");
+}
+
+#[test]
+fn test_unknown_or_malformed_term_is_missing() {
+  let i18n = common::instance(Format::Fluent, FTL);
+  i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(i18n.translate("-nope", &Args::new()), "-nope");
+  assert_eq!(i18n.translate("-brand }{ -brand", &Args::new()), "-brand }{ -brand");
+  // Term attributes are private to selectors in Fluent.
+  assert_eq!(i18n.translate("-brand.gender", &Args::new()), "-brand.gender");
+}
+
+// ---------------------------------------------------------------------------
+// Macros on the default instance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_tr_and_tr_attr() {
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_text("en-GB", FTL).unwrap();
+  egui_i18n::set_language("en-GB");
+
+  assert_eq!(tr!("login-button.tooltip"), "Log in to Acme");
+  assert_eq!(tr_attr!("login-button", "tooltip"), "Log in to Acme");
+  assert_eq!(tr_attr!("login-button", "aria-label", { user: "bob" }), "Log in as bob");
+  assert_eq!(egui_i18n::message_attributes("login-button"), ["tooltip", "aria-label"]);
+}
@@ -0,0 +1,180 @@
+//! Integration tests for the translation cache.
+
+mod common;
+
+use std::sync::Arc;
+
+use egui_i18n::{Args, CacheStats, Format, I18n, MissingKeyPolicy};
+
+#[cfg(feature = "fluent")]
+const EN: &str = "greet = Hello, { $name }!\npair = { $a } { $b }";
+#[cfg(feature = "fluent")]
+const DE: &str = "greet = Hallo, { $name }!";
+
+#[cfg(not(feature = "fluent"))]
+const EN: &str = "greet = Hello, {name}!\npair = {a} {b}";
+#[cfg(not(feature = "fluent"))]
+const DE: &str = "greet = Hallo, {name}!";
+
+fn instance() -> I18n {
+  let i18n = common::instance(Format::default(), EN);
+  i18n.load_translations_from_text("de-DE", DE).unwrap();
+  i18n.set_cache_enabled(true);
+  i18n
+}
+
+fn greet(i18n: &I18n, name: &'static str) -> String {
+  let mut args = Args::new();
+  args.set("name", name);
+  i18n.translate("greet", &args)
+}
+
+fn stats(hits: u64, misses: u64, entries: usize) -> CacheStats {
+  CacheStats { hits, misses, entries }
+}
+
+// ---------------------------------------------------------------------------
+// Hits and misses
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_disabled_by_default() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("en-US", EN).unwrap();
+  i18n.set_language("en-US");
+  greet(&i18n, "Ada");
+  greet(&i18n, "Ada");
+  assert!(!i18n.is_cache_enabled());
+  assert_eq!(i18n.cache_stats(), CacheStats::default());
+}
+
+#[test]
+fn test_repeated_lookups_hit() {
+  let i18n = instance();
+  assert_eq!(greet(&i18n, "Ada"), "Hello, Ada!");
+  assert_eq!(greet(&i18n, "Ada"), "Hello, Ada!");
+  assert_eq!(i18n.cache_stats(), stats(1, 1, 1));
+  assert_eq!(greet(&i18n, "Bob"), "Hello, Bob!");
+  assert_eq!(i18n.cache_stats(), stats(1, 2, 2));
+}
+
+#[test]
+fn test_arguments_are_part_of_the_key() {
+  let i18n = instance();
+  let mut a = Args::new();
+  a.set("a", 1);
+  a.set("b", "x");
+  let mut b = Args::new();
+  b.set("b", "x");
+  b.set("a", 1);
+  let mut c = Args::new();
+  c.set("a", "1");
+  c.set("b", "x");
+  i18n.translate("pair", &a);
+  i18n.translate("pair", &b);
+  assert_eq!(i18n.cache_stats(), stats(1, 1, 1));
+  // The same text, but a string instead of a number.
+  i18n.translate("pair", &c);
+  assert_eq!(i18n.cache_stats(), stats(1, 2, 2));
+}
+
+#[test]
+fn test_swapped_values_are_different_arguments() {
+  let i18n = instance();
+  let mut a = Args::new();
+  a.set("a", "x");
+  a.set("b", "y");
+  let mut b = Args::new();
+  b.set("a", "y");
+  b.set("b", "x");
+  assert_eq!(i18n.translate("pair", &a), "x y");
+  assert_eq!(i18n.translate("pair", &b), "y x");
+  assert_eq!(i18n.translate("pair", &a), "x y");
+  assert_eq!(i18n.cache_stats(), stats(1, 2, 2));
+}
+
+#[test]
+fn test_shared_results() {
+  let i18n = instance();
+  let mut args = Args::new();
+  args.set("name", "Ada");
+  let first = i18n.translate_shared("greet", &args);
+  let second = i18n.translate_shared("greet", &args);
+  assert!(Arc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn test_missing_keys_are_not_cached() {
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(i18n.translate("nope", &Args::new()), "nope");
+  assert_eq!(i18n.translate("nope", &Args::new()), "nope");
+  assert_eq!(i18n.cache_stats(), stats(0, 2, 0));
+}
+
+#[test]
+fn test_clear_and_disable() {
+  let i18n = instance();
+  greet(&i18n, "Ada");
+  greet(&i18n, "Ada");
+  i18n.clear_cache();
+  assert_eq!(i18n.cache_stats(), CacheStats::default());
+
+  greet(&i18n, "Ada");
+  i18n.set_cache_enabled(false);
+  greet(&i18n, "Ada");
+  assert_eq!(i18n.cache_stats(), stats(0, 1, 0));
+}
+
+// ---------------------------------------------------------------------------
+// Invalidation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_language_change_invalidates() {
+  let i18n = instance();
+  assert_eq!(greet(&i18n, "Ada"), "Hello, Ada!");
+  i18n.set_language("de-DE");
+  assert_eq!(greet(&i18n, "Ada"), "Hallo, Ada!");
+  i18n.set_fallbacks(&["en-US"]);
+  assert_eq!(i18n.cache_stats().entries, 0);
+  // `pair` comes from the fallback.
+  let mut args = Args::new();
+  args.set("a", 1);
+  args.set("b", 2);
+  assert_eq!(i18n.translate("pair", &args), "1 2");
+  assert_eq!(i18n.translate("pair", &args), "1 2");
+  assert_eq!(i18n.cache_stats(), stats(1, 3, 1));
+}
+
+#[test]
+fn test_reload_invalidates() {
+  let i18n = instance();
+  assert_eq!(greet(&i18n, "Ada"), "Hello, Ada!");
+  i18n.load_translations_from_text("en-US", EN.replace("Hello", "Hi")).unwrap();
+  assert_eq!(greet(&i18n, "Ada"), "Hi, Ada!");
+}
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_classic_map_invalidates() {
+  let i18n = instance();
+  let map = [("title".to_string(), "Old".to_string())].into_iter().collect();
+  i18n.load_translations_from_map("fr-FR", map).unwrap();
+  i18n.set_language("fr-FR");
+  assert_eq!(i18n.translate("title", &Args::new()), "Old");
+  let map = [("title".to_string(), "New".to_string())].into_iter().collect();
+  i18n.load_translations_from_map("fr-FR", map).unwrap();
+  assert_eq!(i18n.translate("title", &Args::new()), "New");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_fluent_resources_invalidate() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "extra", "extra = One").unwrap();
+  assert_eq!(i18n.translate("extra", &Args::new()), "One");
+  i18n.remove_resource("en-US", "extra");
+  i18n.add_resource("en-US", "more", "extra = Two").unwrap();
+  assert_eq!(i18n.translate("extra", &Args::new()), "Two");
+}
@@ -0,0 +1,129 @@
+//! Integration tests for the generated message functions.
+//!
+//! `tests/codegen/msgs.rs` is the module generated from the catalogs next to
+//! it; it is compiled here and compared with a fresh generation, so it has to
+//! be regenerated when the generator changes:
+//!
+//! ```text
+//! cargo run -p egui-i18n-cli -- codegen --source-path i18n/tests/codegen \
+//!   --language en-US --output-path i18n/tests/codegen/msgs.rs
+//! ```
+//!
+//! Only `test_generated_functions_translate` uses the default instance.
+#![cfg(feature = "codegen")]
+
+use std::path::{Path, PathBuf};
+
+use egui_i18n::codegen::Catalog;
+use egui_i18n::{Format, I18nError};
+
+#[allow(dead_code)]
+mod msgs {
+  include!("codegen/msgs.rs");
+}
+
+fn fixtures() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/codegen")
+}
+
+fn catalog() -> Catalog {
+  Catalog::read(fixtures(), "en-US").unwrap()
+}
+
+// ---------------------------------------------------------------------------
+// Reading catalogs
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_reads_every_file_of_the_language() {
+  let catalog = catalog();
+  let files: Vec<_> =
+    catalog.files.iter().map(|file| file.file_name().unwrap().to_str().unwrap()).collect();
+  assert_eq!(files, ["en-US.egl", "en_US.ftl"]);
+  assert!(catalog.messages.contains_key("welcome"));
+  assert!(catalog.messages.contains_key("inbox"));
+  assert!(!catalog.messages.contains_key("only-in-german"));
+}
+
+#[test]
+fn test_placeholders_in_order_of_first_use() {
+  let catalog = catalog();
+  let placeholders = |key: &str| catalog.messages[key].placeholders.clone();
+  assert_eq!(placeholders("welcome"), Vec::<String>::new());
+  assert_eq!(placeholders("hello-name"), ["name"]);
+  assert_eq!(placeholders("files"), ["count", "folder"]);
+  assert_eq!(placeholders("inbox"), ["count"]);
+  assert_eq!(placeholders("login.tooltip"), ["user"]);
+  // Through the referenced message, where it is referenced.
+  assert_eq!(placeholders("greeting"), ["user", "place"]);
+  // Variables of a term are not the message's.
+  assert_eq!(placeholders("about"), Vec::<String>::new());
+}
+
+#[test]
+fn test_fluent_wins_over_classic() {
+  let catalog = catalog();
+  assert_eq!(catalog.messages["shadowed"].text, "Fluent text");
+  assert_eq!(catalog.messages["hello-name"].text, "Hello, {name}!");
+}
+
+#[test]
+fn test_missing_language_is_not_found() {
+  match Catalog::read(fixtures(), "fr-FR") {
+    Err(I18nError::Io { source, .. }) => {
+      assert_eq!(source.kind(), std::io::ErrorKind::NotFound)
+    },
+    other => panic!("expected a not found error, got {:?}", other.map(|c| c.files)),
+  }
+  assert!(matches!(
+    Catalog::read(fixtures().join("does-not-exist"), "en-US"),
+    Err(I18nError::Io { .. })
+  ));
+}
+
+// ---------------------------------------------------------------------------
+// Generated module
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_generated_module_is_up_to_date() {
+  let expected = std::fs::read_to_string(fixtures().join("msgs.rs")).unwrap();
+  assert_eq!(catalog().to_rust(), expected.replace("\r\n", "\n"));
+}
+
+#[test]
+fn test_names_are_valid_identifiers() {
+  let rust = catalog().to_rust();
+  assert!(rust.contains("pub fn hello_name<'a>(name: impl ::egui_i18n::IntoArg<'a>)"));
+  assert!(rust.contains("pub fn login_tooltip<'a>("));
+  assert!(rust.contains("pub fn r#type() -> ::std::string::String"));
+  // `args` is the generated local; `fileName` is snake cased.
+  assert!(rust.contains("    args_2: impl ::egui_i18n::IntoArg<'a>,\n"));
+  assert!(rust.contains("    args.set(\"args\", args_2);\n"));
+  assert!(rust.contains("    args.set(\"fileName\", file_name);\n"));
+  // Terms are not messages.
+  assert!(!rust.contains("fn brand"));
+}
+
+#[test]
+fn test_empty_catalog() {
+  assert_eq!(
+    Catalog::default().to_rust(),
+    "// Generated by egui-i18n from no files. Do not edit.\n"
+  );
+}
+
+#[test]
+fn test_generated_functions_translate() {
+  let content = std::fs::read_to_string(fixtures().join("en-US.egl")).unwrap();
+  egui_i18n::load_translations_from_text_with_format("en-US", content, Format::Classic).unwrap();
+  egui_i18n::set_language("en-US");
+
+  assert_eq!(msgs::welcome(), "Welcome");
+  assert_eq!(msgs::hello_name("Ferris"), "Hello, Ferris!");
+  assert_eq!(msgs::files(1, "docs"), "1 file in docs");
+  assert_eq!(msgs::files(3, "docs"), "3 files in docs");
+  assert_eq!(msgs::save_as("a", "b.txt"), "Save a as b.txt");
+  assert_eq!(msgs::r#type(), "Type");
+  assert_eq!(msgs::menu_open(), "Open…");
+}
@@ -0,0 +1,11 @@
+shadowed = Fluent text
+inbox = { $count ->
+    [one] One message
+   *[other] { $count } messages
+}
+hello-user = Hi { $user }
+greeting = { hello-user }, welcome to { $place }
+login = Log in
+    .tooltip = Sign in as { $user }
+-brand = Egui
+about = About { -brand }
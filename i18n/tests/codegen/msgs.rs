@@ -0,0 +1,147 @@
+// Generated by egui-i18n from en-US.egl, en_US.ftl. Do not edit.
+
+/// `about`
+///
+/// ```text
+/// About { -brand }
+/// ```
+pub fn about() -> ::std::string::String {
+    ::egui_i18n::translate("about", &::egui_i18n::Args::new())
+}
+
+/// `files`
+///
+/// ```text
+/// {count, plural, one {# file} other {# files}} in {folder}
+/// ```
+pub fn files<'a>(
+    count: impl ::egui_i18n::IntoArg<'a>,
+    folder: impl ::egui_i18n::IntoArg<'a>,
+) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("count", count);
+    args.set("folder", folder);
+    ::egui_i18n::translate("files", &args)
+}
+
+/// `greeting`
+///
+/// ```text
+/// { hello-user }, welcome to { $place }
+/// ```
+pub fn greeting<'a>(
+    user: impl ::egui_i18n::IntoArg<'a>,
+    place: impl ::egui_i18n::IntoArg<'a>,
+) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("user", user);
+    args.set("place", place);
+    ::egui_i18n::translate("greeting", &args)
+}
+
+/// `hello-name`
+///
+/// ```text
+/// Hello, {name}!
+/// ```
+pub fn hello_name<'a>(name: impl ::egui_i18n::IntoArg<'a>) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("name", name);
+    ::egui_i18n::translate("hello-name", &args)
+}
+
+/// `hello-user`
+///
+/// ```text
+/// Hi { $user }
+/// ```
+pub fn hello_user<'a>(user: impl ::egui_i18n::IntoArg<'a>) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("user", user);
+    ::egui_i18n::translate("hello-user", &args)
+}
+
+/// `inbox`
+///
+/// ```text
+/// { $count ->
+///     [one] One message
+///    *[other] { $count } messages
+/// }
+/// ```
+pub fn inbox<'a>(count: impl ::egui_i18n::IntoArg<'a>) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("count", count);
+    ::egui_i18n::translate("inbox", &args)
+}
+
+/// `login`
+///
+/// ```text
+/// Log in
+/// ```
+pub fn login() -> ::std::string::String {
+    ::egui_i18n::translate("login", &::egui_i18n::Args::new())
+}
+
+/// `login.tooltip`
+///
+/// ```text
+/// Sign in as { $user }
+/// ```
+pub fn login_tooltip<'a>(user: impl ::egui_i18n::IntoArg<'a>) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("user", user);
+    ::egui_i18n::translate("login.tooltip", &args)
+}
+
+/// `[menu] Open`
+///
+/// ```text
+/// Open…
+/// ```
+pub fn menu_open() -> ::std::string::String {
+    ::egui_i18n::translate("menu\u{4}Open", &::egui_i18n::Args::new())
+}
+
+/// `save-as`
+///
+/// ```text
+/// Save {args} as {fileName}
+/// ```
+pub fn save_as<'a>(
+    args_2: impl ::egui_i18n::IntoArg<'a>,
+    file_name: impl ::egui_i18n::IntoArg<'a>,
+) -> ::std::string::String {
+    let mut args = ::egui_i18n::Args::new();
+    args.set("args", args_2);
+    args.set("fileName", file_name);
+    ::egui_i18n::translate("save-as", &args)
+}
+
+/// `shadowed`
+///
+/// ```text
+/// Fluent text
+/// ```
+pub fn shadowed() -> ::std::string::String {
+    ::egui_i18n::translate("shadowed", &::egui_i18n::Args::new())
+}
+
+/// `type`
+///
+/// ```text
+/// Type
+/// ```
+pub fn r#type() -> ::std::string::String {
+    ::egui_i18n::translate("type", &::egui_i18n::Args::new())
+}
+
+/// `welcome`
+///
+/// ```text
+/// Welcome
+/// ```
+pub fn welcome() -> ::std::string::String {
+    ::egui_i18n::translate("welcome", &::egui_i18n::Args::new())
+}
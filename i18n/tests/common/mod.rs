@@ -0,0 +1,55 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Catalog content sticks to `key = value` lines, which parse the same in the
+//! classic and the Fluent syntax.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use egui_i18n::{Format, I18n};
+
+/// The file extension of the backend compiled in.
+pub fn ext() -> &'static str {
+  if cfg!(feature = "fluent") {
+    "ftl"
+  } else {
+    "egl"
+  }
+}
+
+/// `<stem>.<ext>` for the backend compiled in.
+pub fn file_name(stem: &str) -> String {
+  format!("{}.{}", stem, ext())
+}
+
+/// An empty `egui-i18n-<suite>-<name>-<pid>` directory under the system temp
+/// directory.
+pub fn temp_dir(suite: &str, name: &str) -> PathBuf {
+  let dir =
+    std::env::temp_dir().join(format!("egui-i18n-{}-{}-{}", suite, name, std::process::id()));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+  dir
+}
+
+/// Writes `content` to `<path>.<ext>` under `dir`, creating any parent
+/// directories.
+pub fn write(dir: &Path, path: &str, content: impl AsRef<[u8]>) -> PathBuf {
+  let file = dir.join(file_name(path));
+  fs::create_dir_all(file.parent().unwrap()).unwrap();
+  fs::write(&file, content).unwrap();
+  file
+}
+
+/// A translator with `content` loaded for `en-US` in `format`, and `en-US`
+/// selected. Fluent's isolation marks are off, so results compare as written.
+pub fn instance(format: Format, content: &str) -> I18n {
+  let i18n = I18n::new();
+  #[cfg(feature = "fluent")]
+  i18n.set_use_isolating(false);
+  i18n.load_translations_from_text_with_format("en-US", content, format).unwrap();
+  i18n.set_language("en-US");
+  i18n
+}
@@ -0,0 +1,86 @@
+//! Integration tests for the per-`egui::Context` translator storage.
+
+#![cfg(feature = "egui")]
+
+use egui_i18n::egui::Context;
+use egui_i18n::{I18n, I18nContextExt};
+
+const CONTENT: &str = "title = Title";
+
+#[test]
+fn test_context_i18n_is_created_once() {
+  let ctx = Context::default();
+  let first = ctx.i18n();
+  let second = ctx.i18n();
+  assert!(first.ptr_eq(&second));
+}
+
+#[test]
+fn test_contexts_keep_separate_language_and_fallback() {
+  let a = Context::default();
+  let b = Context::default();
+  a.i18n().set_language("en-US");
+  a.i18n().set_fallback("en-GB");
+  b.i18n().set_language("ja-JP");
+
+  assert_eq!(a.i18n().get_language(), "en-US");
+  assert_eq!(a.i18n().get_fallback(), "en-GB");
+  assert_eq!(b.i18n().get_language(), "ja-JP");
+  assert_eq!(b.i18n().get_fallback(), "");
+}
+
+#[test]
+fn test_contexts_keep_separate_catalogs() {
+  let a = Context::default();
+  let b = Context::default();
+  a.i18n().load_translations_from_text("en-US", CONTENT).unwrap();
+  b.i18n().load_translations_from_text("ja-JP", CONTENT).unwrap();
+
+  assert_eq!(a.i18n().languages(), vec!["en-US".to_string()]);
+  assert_eq!(b.i18n().languages(), vec!["ja-JP".to_string()]);
+}
+
+#[test]
+fn test_context_is_independent_of_default_instance() {
+  let ctx = Context::default();
+  ctx.i18n().load_translations_from_text("eo", CONTENT).unwrap();
+  assert!(!egui_i18n::languages().contains(&"eo".to_string()));
+}
+
+#[test]
+fn test_set_i18n_shares_translator_between_contexts() {
+  let shared = I18n::new();
+  shared.set_language("de-DE");
+  let a = Context::default();
+  let b = Context::default();
+  a.set_i18n(shared.clone());
+  b.set_i18n(shared);
+
+  b.i18n().set_language("fr-FR");
+  assert_eq!(a.i18n().get_language(), "fr-FR");
+}
+
+#[cfg(not(feature = "fluent"))]
+#[test]
+fn test_context_translates_classic() {
+  let ctx = Context::default();
+  let i18n = ctx.i18n();
+  i18n.load_translations_from_text("en-US", "hi = Hi, {name}!").unwrap();
+  i18n.set_language("en-US");
+  let mut args = std::collections::HashMap::new();
+  args.insert("name", "Ada".to_string());
+  assert_eq!(i18n.translate_classic("hi", &args), "Hi, Ada!");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_context_translates_fluent() {
+  let ctx = Context::default();
+  let i18n = ctx.i18n();
+  i18n.set_use_isolating(false);
+  i18n.load_translations_from_text("en-US", "hi = Hi, { $name }!").unwrap();
+  i18n.set_language("en-US");
+  let mut args = egui_i18n::fluent::FluentArgs::new();
+  args.set("name", "Ada");
+  assert_eq!(i18n.translate_fluent("hi", &args), "Hi, Ada!");
+}
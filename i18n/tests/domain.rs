@@ -0,0 +1,187 @@
+//! Integration tests for message domains.
+
+mod common;
+
+use egui_i18n::{tr, Args, FileSource, Format, I18n, LoadMode, MemorySource};
+
+fn instance() -> I18n {
+  let i18n = common::instance(Format::default(), "title = Title\nsave = Save");
+  i18n.load_domain_translations_from_text("plugin-x", "en-US", "save = Save as file").unwrap();
+  i18n
+}
+
+fn tr_in(i18n: &I18n, domain: &str, key: &str) -> String {
+  i18n.translate_in(domain, key, &Args::new())
+}
+
+// ---------------------------------------------------------------------------
+// Lookup
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_domain_keys_do_not_collide_with_the_core() {
+  let i18n = instance();
+  assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save as file");
+  assert_eq!(i18n.translate("save", &Args::new()), "Save");
+  assert_eq!(tr_in(&i18n, "", "save"), "Save");
+}
+
+#[test]
+fn test_domain_falls_back_to_the_core() {
+  let i18n = instance();
+  assert_eq!(tr_in(&i18n, "plugin-x", "title"), "Title");
+  assert_eq!(tr_in(&i18n, "unknown", "save"), "Save");
+  assert_eq!(tr_in(&i18n, "plugin-x", "absent"), "");
+}
+
+#[test]
+fn test_domain_is_searched_across_the_chain_first() {
+  let i18n = instance();
+  i18n.load_translations_from_text("de-DE", "save = Speichern").unwrap();
+  i18n.set_language("de-DE");
+  i18n.set_fallback("en-US");
+  // The domain's English message wins over the core's German one.
+  assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save as file");
+  assert_eq!(i18n.translate("save", &Args::new()), "Speichern");
+}
+
+#[test]
+fn test_domains_are_separate() {
+  let i18n = instance();
+  i18n.load_domain_translations_from_text("plugin-y", "en-US", "save = Save a copy").unwrap();
+  assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save as file");
+  assert_eq!(tr_in(&i18n, "plugin-y", "save"), "Save a copy");
+}
+
+#[test]
+fn test_domain_with_args() {
+  let i18n = instance();
+  i18n
+    .load_domain_translations_from_text("plugin-x", "en-US", "export = Export as {format}")
+    .unwrap();
+  let mut args = Args::new();
+  args.set("format", "PNG");
+  let expected = if cfg!(feature = "fluent") { "Export as {format}" } else { "Export as PNG" };
+  assert_eq!(i18n.translate_in("plugin-x", "export", &args), expected);
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_domains_and_their_languages() {
+  let i18n = instance();
+  i18n.load_domain_translations_from_text("plugin-x", "de-DE", "save = Als Datei").unwrap();
+  let domain = String::from("a-plugin");
+  i18n.load_domain_translations_from_text(&domain, "fr-FR", String::from("save = Sauver")).unwrap();
+  assert_eq!(i18n.domains(), ["a-plugin", "plugin-x"]);
+  let mut languages = i18n.domain_languages("plugin-x");
+  languages.sort();
+  assert_eq!(languages, ["de-DE", "en-US"]);
+  assert_eq!(i18n.domain_languages(""), ["en-US"]);
+  assert!(i18n.domain_languages("unknown").is_empty());
+  // Domains do not add to the core languages.
+  assert_eq!(i18n.languages(), ["en-US"]);
+}
+
+#[test]
+fn test_unload_domain() {
+  let i18n = instance();
+  assert!(i18n.unload_domain("plugin-x"));
+  assert!(!i18n.unload_domain("plugin-x"));
+  assert!(!i18n.unload_domain(""));
+  assert!(i18n.domains().is_empty());
+  assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save");
+}
+
+#[test]
+fn test_empty_name_is_the_core_domain() {
+  let i18n = I18n::new();
+  i18n.load_domain_translations_from_text("", "en-US", "title = Core").unwrap();
+  i18n.set_language("en-US");
+  assert!(i18n.domains().is_empty());
+  assert_eq!(i18n.translate("title", &Args::new()), "Core");
+}
+
+#[test]
+fn test_failed_load_creates_no_domain() {
+  let i18n = I18n::new();
+  let missing = std::env::temp_dir().join("egui-i18n-domain-does-not-exist");
+  let source = FileSource::new(missing);
+  let report = i18n.load_domain_from_source_with_report("plugin-x", &source, LoadMode::Lenient);
+  assert!(!report.is_ok());
+  assert!(i18n.domains().is_empty());
+  assert!(i18n.load_domain_from_source("plugin-x", &source).is_err());
+  assert!(i18n.domains().is_empty());
+}
+
+#[test]
+fn test_domain_from_source() {
+  let i18n = I18n::new();
+  let source = MemorySource::new().add("en-US", Default::default(), "title = Plugin");
+  i18n.load_domain_from_source("plugin-x", &source).unwrap();
+  i18n.set_language("en-US");
+  assert_eq!(tr_in(&i18n, "plugin-x", "title"), "Plugin");
+  assert_eq!(i18n.translate("title", &Args::new()), "");
+}
+
+#[test]
+fn test_cache_keeps_domains_apart() {
+  let i18n = instance();
+  i18n.set_cache_enabled(true);
+  for _ in 0..2 {
+    assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save as file");
+    assert_eq!(i18n.translate("save", &Args::new()), "Save");
+  }
+  assert_eq!(i18n.cache_stats().hits, 2);
+
+  i18n.unload_domain("plugin-x");
+  assert_eq!(tr_in(&i18n, "plugin-x", "save"), "Save");
+}
+
+// ---------------------------------------------------------------------------
+// Global
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_global_domain_and_macro() {
+  // A language tag and domain no other test loads.
+  egui_i18n::load_translations_from_text("tlh-Latn", "title = Core\nsave = Save").unwrap();
+  egui_i18n::load_domain_translations_from_text("tlh-plugin", "tlh-Latn", "save = Plugin {name}")
+    .unwrap();
+  assert!(egui_i18n::domains().contains(&"tlh-plugin".to_string()));
+  assert_eq!(egui_i18n::domain_languages("tlh-plugin"), ["tlh-Latn"]);
+
+  egui_i18n::set_language("tlh-Latn");
+  assert_eq!(tr!(domain = "tlh-plugin", "title"), "Core");
+  let expected = if cfg!(feature = "fluent") { "Plugin {name}" } else { "Plugin ada" };
+  assert_eq!(tr!(domain = "tlh-plugin", "save", { name: "ada" }), expected);
+
+  assert!(egui_i18n::unload_domain("tlh-plugin"));
+  assert_eq!(egui_i18n::translate_in("tlh-plugin", "save", &Args::new()), "Save");
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_functions_reach_domains() {
+  use egui_i18n::fluent::FluentValue;
+
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  i18n.load_domain_translations_from_text("before", "en-US", "shout = { SHOUT($name) }").unwrap();
+  i18n.add_function("SHOUT", |positional, _| match positional.first() {
+    Some(FluentValue::String(s)) => FluentValue::from(s.to_uppercase()),
+    _ => FluentValue::None,
+  });
+  i18n.load_domain_translations_from_text("after", "en-US", "shout = { SHOUT($name) }").unwrap();
+  i18n.set_language("en-US");
+  let mut args = Args::new();
+  args.set("name", "ada");
+  assert_eq!(i18n.translate_in("before", "shout", &args), "ADA");
+  assert_eq!(i18n.translate_in("after", "shout", &args), "ADA");
+}
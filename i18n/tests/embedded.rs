@@ -0,0 +1,71 @@
+//! Integration tests for loading translation files embedded at build time.
+//! `include_translations!` itself is tested in the macros crate.
+
+use egui_i18n::{Args, Embedded, EmbeddedFile, Format, I18n};
+
+const fn file(path: &'static str, language: &'static str, content: &'static str) -> EmbeddedFile {
+    let format = if cfg!(feature = "fluent") { Format::Fluent } else { Format::Classic };
+  EmbeddedFile { path, language, format, content }
+}
+
+static TRANSLATIONS: Embedded = Embedded::new(&[
+  file("i18n/de-DE.ftl", "de-DE", "title = Titel"),
+  file("i18n/en-US.ftl", "en-US", "title = Title"),
+]);
+
+#[test]
+fn test_load_embedded() {
+  let i18n = I18n::new();
+  i18n.load_embedded(&TRANSLATIONS).unwrap();
+  assert_eq!(TRANSLATIONS.languages(), ["de-DE", "en-US"]);
+  i18n.set_language("de-DE");
+  assert_eq!(i18n.translate("title", &Args::new()), "Titel");
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate("title", &Args::new()), "Title");
+}
+
+#[test]
+fn test_empty() {
+  let i18n = I18n::new();
+  i18n.load_embedded(&Embedded::default()).unwrap();
+  assert!(i18n.languages().is_empty());
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_files_are_fluent_resources() {
+  static SPLIT: Embedded = Embedded::new(&[
+    file("i18n/en-US/main.ftl", "en-US", "title = Title"),
+    file("i18n/en-US/settings.ftl", "en-US", "theme = Theme"),
+  ]);
+  let i18n = I18n::new();
+  i18n.load_embedded(&SPLIT).unwrap();
+  assert_eq!(i18n.resources("en-US"), ["i18n/en-US/main.ftl", "i18n/en-US/settings.ftl"]);
+  assert_eq!(SPLIT.languages(), ["en-US"]);
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_error_names_the_file() {
+  static BROKEN: Embedded = Embedded::new(&[file("i18n/en-US.ftl", "en-US", "title = Title\n!!!")]);
+  let error = I18n::new().load_embedded(&BROKEN).unwrap_err();
+  assert!(matches!(error, egui_i18n::I18nError::Parse { .. }));
+  assert_eq!(error.path(), Some(std::path::Path::new("i18n/en-US.ftl")));
+}
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_classic_files_are_routed_by_format() {
+  static CLASSIC: Embedded = Embedded::new(&[EmbeddedFile {
+    path: "i18n/en_US.egl",
+    language: "en_US",
+    format: Format::Classic,
+    content: "greeting = Hello, {name}!",
+  }]);
+  let i18n = I18n::new();
+  i18n.load_embedded(&CLASSIC).unwrap();
+  i18n.set_language("en_US");
+  let mut args = Args::new();
+  args.set("name", "Ferris");
+  assert_eq!(i18n.translate("greeting", &args), "Hello, Ferris!");
+}
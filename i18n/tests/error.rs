@@ -0,0 +1,119 @@
+//! Integration tests for the structured `I18nError` returned by the loaders.
+
+mod common;
+
+use std::error::Error;
+
+use egui_i18n::{I18n, I18nError};
+
+// ---------------------------------------------------------------------------
+// I/O
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_missing_directory_is_io_error() {
+  let missing = std::env::temp_dir().join("egui-i18n-error-does-not-exist");
+  let err = I18n::new().load_translations_from_path(missing.to_string_lossy()).unwrap_err();
+  match &err {
+    I18nError::Io { path, source } => {
+      assert_eq!(path, &missing);
+      assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+    },
+    other => panic!("expected Io, got {other:?}"),
+  }
+  assert_eq!(err.path(), Some(missing.as_path()));
+  assert!(err.source().is_some());
+  assert!(err.to_string().contains("egui-i18n-error-does-not-exist"));
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_parse_error_has_line_and_column() {
+  let ftl = "ok = fine\nbroken\nalso-ok = yes";
+  let err = I18n::new().load_translations_from_text("en-US", ftl).unwrap_err();
+  match &err {
+    I18nError::Parse { language, path, errors } => {
+      assert_eq!(language, "en-US");
+      assert!(path.is_none());
+      assert_eq!(errors.len(), 1);
+      assert_eq!((errors[0].line, errors[0].column), (2, 7));
+      assert!(matches!(
+        errors[0].error,
+        egui_i18n::SyntaxError::Fluent(egui_i18n::fluent::FluentError::ParserError(_))
+      ));
+    },
+    other => panic!("expected Parse, got {other:?}"),
+  }
+  assert_eq!(err.language(), Some("en-US"));
+  assert!(err.source().is_some());
+  assert!(err.to_string().contains("line 2, column 7"), "{err}");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_parse_error_column_counts_characters() {
+  // The unclosed placeable is reported at the end of line 2, which is 13
+  // characters but 15 bytes in.
+  let ftl = "ok = fine\nk = { \"größe\"";
+  let err = I18n::new().load_translations_from_text("de-DE", ftl).unwrap_err();
+  let I18nError::Parse { errors, .. } = err else { panic!("expected Parse") };
+  assert_eq!((errors[0].line, errors[0].column), (2, 14));
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_invalid_language_keeps_source() {
+  let err = I18n::new().load_translations_from_text("not a tag", "a = b").unwrap_err();
+  match &err {
+    I18nError::InvalidLanguage { language, .. } => assert_eq!(language, "not a tag"),
+    other => panic!("expected InvalidLanguage, got {other:?}"),
+  }
+  assert!(err.source().is_some());
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_duplicate_message_is_resource_error() {
+  let err = I18n::new().load_translations_from_text("en-US", "dup = one\ndup = two").unwrap_err();
+  match &err {
+    I18nError::Resource { errors, .. } => {
+      assert!(
+        matches!(&errors[0], egui_i18n::fluent::FluentError::Overriding { id, .. } if id == "dup")
+      );
+    },
+    other => panic!("expected Resource, got {other:?}"),
+  }
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_path_is_attached_to_parse_errors() {
+  let dir = common::temp_dir("error", "parse");
+  let file = dir.join("en-US.ftl");
+  std::fs::write(&file, "ok = fine\n= broken").unwrap();
+  let err = I18n::new().load_translations_from_path(dir.to_string_lossy()).unwrap_err();
+  std::fs::remove_dir_all(&dir).unwrap();
+
+  assert!(matches!(err, I18nError::Parse { .. }));
+  assert_eq!(err.path(), Some(file.as_path()));
+  assert_eq!(err.language(), Some("en-US"));
+  assert!(err.to_string().contains("en-US.ftl"), "{err}");
+}
+
+// ---------------------------------------------------------------------------
+// Disabled backends
+// ---------------------------------------------------------------------------
+
+#[cfg(not(feature = "fluent"))]
+#[test]
+fn test_fluent_format_not_enabled() {
+  let err = I18n::new()
+    .load_translations_from_text_with_format("en-US", "a = b", egui_i18n::Format::Fluent)
+    .unwrap_err();
+  assert!(matches!(err, I18nError::FormatNotEnabled { format: egui_i18n::Format::Fluent }));
+  assert!(err.to_string().contains("`fluent` feature"));
+}
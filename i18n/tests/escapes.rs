@@ -0,0 +1,114 @@
+//! Integration tests for escape sequences in the classic format.
+
+use egui_i18n::{escape_key, escape_value, parse_translations};
+
+// ---------------------------------------------------------------------------
+// parse_translations
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_layout_escapes_are_resolved() {
+  let map = parse_translations(
+    "tabs = a\\tb\nlines = one\\ntwo\nspace = \\u{20}indented\nsnow = \\u{2603}\neq = 1 \\= 1",
+    true,
+  );
+  assert_eq!(map["tabs"], "a\tb");
+  assert_eq!(map["lines"], "one\ntwo");
+  assert_eq!(map["space"], " indented");
+  assert_eq!(map["snow"], "☃");
+  assert_eq!(map["eq"], "1 = 1");
+}
+
+#[test]
+fn test_syntax_escapes_are_kept_for_the_formatter() {
+  let map = parse_translations("v = \\{name\\} \\# \\\\ \\u{7b}", true);
+  assert_eq!(map["v"], "\\{name\\} \\# \\\\ \\{");
+}
+
+#[test]
+fn test_keys_are_fully_unescaped() {
+  let map = parse_translations("\\#tag\\{x\\}\\\\ = v\na\\nb = w", true);
+  assert_eq!(map["#tag{x}\\"], "v");
+  assert_eq!(map["a\nb"], "w");
+}
+
+#[test]
+fn test_escaped_hash_and_equals_continue_the_value() {
+  let map = parse_translations("v = first\n\\# not a comment\n1 \\= 1\n# comment\nw = next", true);
+  assert_eq!(map["v"], "first\n\\# not a comment\n1 = 1");
+  assert_eq!(map["w"], "next");
+}
+
+#[test]
+fn test_unknown_escapes_are_kept() {
+  let map = parse_translations("v = C:\\path\\u{zz}\\", true);
+  assert_eq!(map["v"], "C:\\path\\u{zz}\\");
+}
+
+// ---------------------------------------------------------------------------
+// escape_key / escape_value
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_escape_round_trip() {
+  for text in [
+    "plain",
+    "a = b",
+    "# not a comment",
+    " padded\t",
+    "two\nlines",
+    "back\\slash",
+    "Hello, {name}!",
+  ] {
+    let content = format!("{} = {}", escape_key(text), escape_value(text));
+    let map = parse_translations(&content, true);
+    assert_eq!(map.len(), 1, "{content:?}");
+    let (key, value) = map.into_iter().next().unwrap();
+    assert_eq!(key, text, "{content:?}");
+    assert_eq!(value, text, "{content:?}");
+  }
+}
+
+#[test]
+fn test_escape_value_round_trips_parsed_values() {
+  let content = "v = \\u{20}C:\\\\{dir} \\{x\\} \\# \\q\\u{zz}\\n\\t";
+  let value = &parse_translations(content, true)["v"];
+  let map = parse_translations(format!("v = {}", escape_value(value)), true);
+  assert_eq!(&map["v"], value);
+}
+
+#[test]
+fn test_escape_value_doubles_backslashes_the_parser_would_read() {
+  assert_eq!(escape_value("C:\\new"), "C:\\\\new");
+  assert_eq!(escape_value("a\\=b \\u{41}"), "a\\\\=b \\\\u{41}");
+  assert_eq!(escape_value("end\\"), "end\\");
+}
+
+// ---------------------------------------------------------------------------
+// Formatting
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_escaped_braces_are_literal() {
+  use std::collections::HashMap;
+
+  use egui_i18n::{Format, I18n};
+
+  let i18n = I18n::new();
+  i18n
+    .load_translations_from_text_with_format(
+      "en-US",
+      "literal = Use \\{name\\} for {name}\nslash = C:\\\\{dir}\nplural = {n, plural, one {\\# # item} other {\\# # items}}",
+      Format::Classic,
+    )
+    .unwrap();
+  i18n.set_language("en-US");
+  let mut args = HashMap::new();
+  args.insert("name", "Ada".to_string());
+  args.insert("dir", "tmp".to_string());
+  args.insert("n", "2".to_string());
+  assert_eq!(i18n.translate_classic("literal", &args), "Use {name} for Ada");
+  assert_eq!(i18n.translate_classic("slash", &args), "C:\\tmp");
+  assert_eq!(i18n.translate_classic("plural", &args), "# 2 items");
+}
@@ -72,17 +72,20 @@ fn test_set_use_isolating_false() {
 }
 
 // ---------------------------------------------------------------------------
-// load_translations_from_map — must return Err in fluent mode
+// load_translations_from_map — must return Err without the classic backend
 // ---------------------------------------------------------------------------
 
+#[cfg(not(feature = "classic"))]
 #[test]
 fn test_load_from_map_returns_err() {
   let mut map = std::collections::HashMap::new();
   map.insert("key".to_string(), "value".to_string());
   let result = egui_i18n::load_translations_from_map("en-US", map);
-  assert!(result.is_err(), "expected Err in fluent mode, got Ok");
-  let msg = result.unwrap_err();
-  assert!(msg.contains("fluent"), "error message should mention 'fluent': {msg}");
+  assert!(result.is_err(), "expected Err without classic, got Ok");
+  let err = result.unwrap_err();
+  assert!(matches!(err, egui_i18n::I18nError::FormatNotEnabled { .. }));
+  let msg = err.to_string();
+  assert!(msg.contains("classic"), "error message should mention 'classic': {msg}");
 }
 
 // ---------------------------------------------------------------------------
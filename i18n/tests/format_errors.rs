@@ -0,0 +1,161 @@
+//! Integration tests for Fluent formatting errors: `try_translate` and the
+//! format error hook.
+#![cfg(feature = "fluent")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use egui_i18n::fluent::{FluentArgs, FluentError};
+use egui_i18n::fluent_bundle::resolver::ResolverError;
+use egui_i18n::{tr, Args, Format, FormatErrorHook, I18n};
+
+const FTL: &str = "
+ok = Fine
+hello = Hello, { $name }!
+broken-ref = See { missing-message }
+cycle = { cycle }
+";
+
+fn instance(hook: FormatErrorHook) -> I18n {
+  let i18n = common::instance(Format::Fluent, FTL);
+  i18n.set_format_error_hook(hook);
+  i18n
+}
+
+// ---------------------------------------------------------------------------
+// try_translate
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_try_translate_clean_message_has_no_errors() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  assert_eq!(i18n.try_translate("ok", &Args::new()), ("Fine".to_string(), vec![]));
+}
+
+#[test]
+fn test_try_translate_missing_variable() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  let (value, errors) = i18n.try_translate("hello", &Args::new());
+  assert_eq!(value, "Hello, {$name}!");
+  assert_eq!(errors.len(), 1);
+  assert!(matches!(&errors[0], FluentError::ResolverError(ResolverError::Reference(_))));
+}
+
+#[test]
+fn test_try_translate_unknown_reference() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  let (value, errors) = i18n.try_translate("broken-ref", &Args::new());
+  assert_eq!(value, "See {missing-message}");
+  assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn test_try_translate_cyclic_reference() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  let (_, errors) = i18n.try_translate("cycle", &Args::new());
+  assert!(errors.iter().any(|e| matches!(e, FluentError::ResolverError(ResolverError::Cyclic))));
+}
+
+#[test]
+fn test_try_translate_missing_key() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  assert_eq!(i18n.try_translate("nope", &Args::new()), (String::new(), vec![]));
+}
+
+// ---------------------------------------------------------------------------
+// Hooks
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_default_hook_is_log() {
+  assert!(matches!(I18n::new().get_format_error_hook(), FormatErrorHook::Log));
+}
+
+#[test]
+fn test_collect_keeps_each_key_once() {
+  let i18n = instance(FormatErrorHook::Collect);
+  assert_eq!(i18n.translate("hello", &Args::new()), "Hello, {$name}!");
+  i18n.translate("hello", &Args::new());
+  i18n.translate("ok", &Args::new());
+
+  let collected = i18n.take_format_errors();
+  assert_eq!(collected.len(), 1);
+  assert_eq!(collected[0].language, "en-US");
+  assert_eq!(collected[0].key, "hello");
+  assert_eq!(collected[0].errors.len(), 1);
+  assert!(collected[0].to_string().contains("\"hello\""), "{}", collected[0]);
+  assert!(i18n.take_format_errors().is_empty());
+}
+
+#[test]
+fn test_collect_reports_again_after_reload() {
+  let i18n = instance(FormatErrorHook::Collect);
+  i18n.translate("hello", &Args::new());
+  assert_eq!(i18n.take_format_errors().len(), 1);
+  i18n.translate("hello", &Args::new());
+  assert!(i18n.take_format_errors().is_empty());
+
+  i18n.load_translations_from_text("en-US", FTL).unwrap();
+  i18n.translate("hello", &Args::new());
+  assert_eq!(i18n.take_format_errors().len(), 1);
+}
+
+#[test]
+fn test_custom_hook_sees_every_occurrence() {
+  let seen = Arc::new(Mutex::new(vec![]));
+  let sink = seen.clone();
+  let i18n = instance(FormatErrorHook::custom(move |report| {
+    sink.lock().unwrap().push(report.key.clone());
+  }));
+  i18n.translate("hello", &Args::new());
+  i18n.translate("hello", &Args::new());
+  i18n.translate_fluent("broken-ref", &FluentArgs::new());
+  i18n.translate("ok", &Args::new());
+  assert_eq!(*seen.lock().unwrap(), ["hello", "hello", "broken-ref"]);
+}
+
+#[test]
+fn test_ignore_hook_collects_nothing() {
+  let i18n = instance(FormatErrorHook::Ignore);
+  i18n.translate("hello", &Args::new());
+  assert!(i18n.take_format_errors().is_empty());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "failed to format \"hello\"")]
+fn test_panic_in_debug_hook_panics() {
+  let i18n = instance(FormatErrorHook::PanicInDebug);
+  i18n.translate("hello", &Args::new());
+}
+
+#[test]
+fn test_panic_in_debug_hook_ignores_clean_messages() {
+  let i18n = instance(FormatErrorHook::PanicInDebug);
+  let mut args = FluentArgs::new();
+  args.set("name", "Ada");
+  assert_eq!(i18n.translate_fluent("hello", &args), "Hello, Ada!");
+}
+
+// ---------------------------------------------------------------------------
+// tr! on the default instance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_tr_reports_through_the_global_hook() {
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_text("en-US", FTL).unwrap();
+  egui_i18n::set_language("en-US");
+  egui_i18n::set_format_error_hook(FormatErrorHook::Collect);
+
+  assert_eq!(tr!("hello", { name: "Ada" }), "Hello, Ada!");
+  assert!(egui_i18n::take_format_errors().is_empty());
+  assert_eq!(tr!("hello"), "Hello, {$name}!");
+  let collected = egui_i18n::take_format_errors();
+  assert_eq!(collected.len(), 1);
+  assert_eq!(collected[0].key, "hello");
+
+  let (_, errors) = egui_i18n::try_translate("broken-ref", &Args::new());
+  assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,209 @@
+//! Integration tests for Fluent functions and locale-aware formatting.
+#![cfg(feature = "fluent")]
+
+use egui_i18n::fluent::FluentValue;
+use egui_i18n::{tr, Args, I18n, IntoArg};
+
+const FTL: &str = r#"
+plain = { $n }
+year = Year { $n }
+number = { NUMBER($n) }
+fraction = { NUMBER($n, minimumFractionDigits: 2) }
+ungrouped = { NUMBER($n, useGrouping: "false") }
+padded = { NUMBER($n, minimumIntegerDigits: 3) }
+significant = { NUMBER($n, maximumSignificantDigits: 3) }
+percent = { NUMBER($n, style: "percent") }
+price = { NUMBER($n, style: "currency", currency: "EUR") }
+price-code = { NUMBER($n, style: "currency", currency: "USD", currencyDisplay: "code") }
+items = { $n ->
+    [one] One item
+   *[other] { NUMBER($n) } items
+}
+
+date = { DATETIME($when) }
+date-long = { DATETIME($when, dateStyle: "long") }
+time = { DATETIME($when, timeStyle: "short") }
+date-time = { DATETIME($when, dateStyle: "short", timeStyle: "medium") }
+
+shout = { SHOUT($name) }
+"#;
+
+// 2025-01-31T15:04:05Z
+const WHEN: i64 = 1_738_335_845;
+
+fn instance(languages: &[&str]) -> I18n {
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  for language in languages {
+    i18n.load_translations_from_text(language, FTL).unwrap();
+  }
+  i18n.set_language(languages[0]);
+  i18n
+}
+
+fn number(i18n: &I18n, key: &str, n: f64) -> String {
+  let mut args = Args::new();
+  args.set("n", n);
+  i18n.translate(key, &args)
+}
+
+fn date(i18n: &I18n, key: &str, when: impl IntoArg<'static>) -> String {
+  let mut args = Args::new();
+  args.set("when", when);
+  i18n.translate(key, &args)
+}
+
+fn shout<'a>(positional: &[FluentValue<'a>], _: &egui_i18n::fluent::FluentArgs) -> FluentValue<'a> {
+  match positional.first() {
+    Some(FluentValue::String(value)) => FluentValue::from(value.to_uppercase()),
+    _ => FluentValue::Error,
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Numbers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_numbers_follow_the_locale() {
+  let i18n = instance(&["en-US", "de-DE", "fr-FR", "de-CH"]);
+  assert_eq!(number(&i18n, "number", 1234567.891), "1,234,567.891");
+  assert_eq!(number(&i18n, "number", -0.5), "-0.5");
+  i18n.set_language("de-DE");
+  assert_eq!(number(&i18n, "number", 1234567.891), "1.234.567,891");
+  i18n.set_language("fr-FR");
+  assert_eq!(number(&i18n, "number", 1234567.891), "1\u{202f}234\u{202f}567,891");
+  i18n.set_language("de-CH");
+  assert_eq!(number(&i18n, "number", 1234.5), "1’234.5");
+}
+
+#[test]
+fn test_plain_placeables_are_not_localized() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  assert_eq!(number(&i18n, "year", 2024.0), "Year 2024");
+  assert_eq!(number(&i18n, "plain", 1234.5), "1234.5");
+  i18n.set_language("de-DE");
+  assert_eq!(number(&i18n, "year", 2024.0), "Year 2024");
+}
+
+#[test]
+fn test_number_options() {
+  let i18n = instance(&["en-US"]);
+  assert_eq!(number(&i18n, "number", 2.0 / 3.0), "0.667");
+  assert_eq!(number(&i18n, "fraction", 3.0), "3.00");
+  assert_eq!(number(&i18n, "ungrouped", 12345.0), "12345");
+  assert_eq!(number(&i18n, "padded", 7.0), "007");
+  assert_eq!(number(&i18n, "significant", 123456.0), "123,000");
+  assert_eq!(number(&i18n, "significant", 0.012345), "0.0123");
+}
+
+#[test]
+fn test_percent_and_currency() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  assert_eq!(number(&i18n, "percent", 0.25), "25%");
+  assert_eq!(number(&i18n, "price", 1234.5), "€1,234.50");
+  assert_eq!(number(&i18n, "price", -3.0), "-€3.00");
+  assert_eq!(number(&i18n, "price-code", 1234.5), "USD\u{a0}1,234.50");
+  i18n.set_language("de-DE");
+  assert_eq!(number(&i18n, "percent", 0.25), "25\u{a0}%");
+  assert_eq!(number(&i18n, "price", 1234.5), "1.234,50\u{a0}€");
+}
+
+#[test]
+fn test_plural_selection_is_unaffected() {
+  let i18n = instance(&["en-US"]);
+  assert_eq!(number(&i18n, "items", 1.0), "One item");
+  assert_eq!(number(&i18n, "items", 1500.0), "1,500 items");
+}
+
+// ---------------------------------------------------------------------------
+// Dates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_datetime_follows_the_locale() {
+  let i18n = instance(&["en-US", "en-GB", "de-DE", "ja-JP", "es-ES"]);
+  assert_eq!(date(&i18n, "date", WHEN as f64), "1/31/2025");
+  assert_eq!(date(&i18n, "date-long", WHEN as f64), "January 31, 2025");
+  assert_eq!(date(&i18n, "time", WHEN as f64), "3:04\u{a0}PM");
+  assert_eq!(date(&i18n, "date-time", WHEN as f64), "1/31/2025, 3:04:05\u{a0}PM");
+
+  i18n.set_language("en-GB");
+  assert_eq!(date(&i18n, "date", WHEN as f64), "31/01/2025");
+  assert_eq!(date(&i18n, "date-long", WHEN as f64), "31 January 2025");
+  assert_eq!(date(&i18n, "time", WHEN as f64), "15:04");
+
+  i18n.set_language("de-DE");
+  assert_eq!(date(&i18n, "date", WHEN as f64), "31.01.2025");
+  assert_eq!(date(&i18n, "date-long", WHEN as f64), "31. Januar 2025");
+  assert_eq!(date(&i18n, "date-time", WHEN as f64), "31.01.2025 15:04:05");
+
+  i18n.set_language("ja-JP");
+  assert_eq!(date(&i18n, "date", WHEN as f64), "2025/01/31");
+  // No month names for Japanese: the long style falls back to the short one.
+  assert_eq!(date(&i18n, "date-long", WHEN as f64), "2025/01/31");
+
+  i18n.set_language("es-ES");
+  assert_eq!(date(&i18n, "date-long", WHEN as f64), "31 de enero de 2025");
+}
+
+#[test]
+fn test_datetime_accepts_iso_strings() {
+  let i18n = instance(&["en-GB"]);
+  assert_eq!(date(&i18n, "date-time", "2025-01-31T15:04:05Z"), "31/01/2025, 15:04:05");
+  assert_eq!(date(&i18n, "date", "1969-12-31"), "31/12/1969");
+  assert_eq!(date(&i18n, "date", "2024-02-29"), "29/02/2024");
+  // Fluent shows a failed call as the call itself.
+  assert_eq!(date(&i18n, "date", "not a date"), "DATETIME()");
+  assert_eq!(date(&i18n, "date", "99999999999999-01-01"), "DATETIME()");
+}
+
+// ---------------------------------------------------------------------------
+// App-defined functions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_added_function_applies_to_loaded_and_future_bundles() {
+  let i18n = instance(&["en-US"]);
+  let mut args = Args::new();
+  args.set("name", "ada");
+  // Unknown until registered.
+  assert_ne!(i18n.translate("shout", &args), "ADA");
+
+  i18n.add_function("SHOUT", shout);
+  assert_eq!(i18n.translate("shout", &args), "ADA");
+
+  i18n.load_translations_from_text("de-DE", FTL).unwrap();
+  i18n.set_language("de-DE");
+  assert_eq!(i18n.translate("shout", &args), "ADA");
+}
+
+#[test]
+fn test_functions_are_per_instance() {
+  let i18n = instance(&["en-US"]);
+  let other = instance(&["en-US"]);
+  i18n.add_function("SHOUT", shout);
+  let mut args = Args::new();
+  args.set("name", "ada");
+  assert_eq!(i18n.translate("shout", &args), "ADA");
+  assert_ne!(other.translate("shout", &args), "ADA");
+}
+
+#[test]
+fn test_builtin_can_be_replaced() {
+  let i18n = instance(&["en-US"]);
+  i18n.add_function("NUMBER", |_, _| FluentValue::from("n/a"));
+  assert_eq!(number(&i18n, "fraction", 3.0), "n/a");
+  // Plain placeables do not call NUMBER.
+  assert_eq!(number(&i18n, "plain", 3.0), "3");
+}
+
+#[test]
+fn test_tr_with_function() {
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_text("en-AU", FTL).unwrap();
+  egui_i18n::set_language("en-AU");
+  egui_i18n::add_function("SHOUT", shout);
+  assert_eq!(tr!("shout", { name: "bob" }), "BOB");
+  assert_eq!(tr!("number", { n: 1234.5 }), "1,234.5");
+}
@@ -0,0 +1,94 @@
+//! Integration tests for owned `I18n` instances.
+
+use egui_i18n::I18n;
+
+const CONTENT: &str = "title = Title";
+
+fn assert_send_sync_clone<T: Send + Sync + Clone + 'static>() {}
+
+#[test]
+fn test_i18n_is_send_sync_clone() {
+  assert_send_sync_clone::<I18n>();
+}
+
+#[test]
+fn test_new_instance_is_empty() {
+  let i18n = I18n::new();
+  assert_eq!(i18n.get_language(), "");
+  assert_eq!(i18n.get_fallback(), "");
+  assert!(i18n.languages().is_empty());
+}
+
+#[test]
+fn test_instances_are_independent() {
+  let a = I18n::new();
+  let b = I18n::new();
+  a.set_language("en-US");
+  a.load_translations_from_text("en-US", CONTENT).unwrap();
+  b.set_language("ja-JP");
+
+  assert_eq!(a.get_language(), "en-US");
+  assert_eq!(b.get_language(), "ja-JP");
+  assert_eq!(a.languages(), vec!["en-US".to_string()]);
+  assert!(b.languages().is_empty());
+  assert!(!a.ptr_eq(&b));
+}
+
+#[test]
+fn test_clones_share_state() {
+  let a = I18n::new();
+  let b = a.clone();
+  b.set_fallback("en-GB");
+  b.load_translations_from_text("en-GB", CONTENT).unwrap();
+
+  assert!(a.ptr_eq(&b));
+  assert_eq!(a.get_fallback(), "en-GB");
+  assert_eq!(a.languages(), vec!["en-GB".to_string()]);
+}
+
+#[test]
+fn test_instance_does_not_touch_global() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("haw-US", CONTENT).unwrap();
+  assert!(!egui_i18n::languages().contains(&"haw-US".to_string()));
+  assert!(!I18n::global().ptr_eq(&i18n));
+}
+
+#[test]
+fn test_global_backs_free_functions() {
+  egui_i18n::load_translations_from_text("gd-GB", CONTENT).unwrap();
+  assert!(I18n::global().languages().contains(&"gd-GB".to_string()));
+}
+
+#[test]
+fn test_instance_usable_across_threads() {
+  let i18n = I18n::new();
+  let worker = i18n.clone();
+  std::thread::spawn(move || worker.load_translations_from_text("en-US", CONTENT).unwrap())
+    .join()
+    .unwrap();
+  assert_eq!(i18n.languages(), vec!["en-US".to_string()]);
+}
+
+#[cfg(not(feature = "fluent"))]
+#[test]
+fn test_instance_translates_classic() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("en-US", "hi = Hi, {name}!").unwrap();
+  i18n.set_language("en-US");
+  let mut args = std::collections::HashMap::new();
+  args.insert("name", "Ada".to_string());
+  assert_eq!(i18n.translate_classic("hi", &args), "Hi, Ada!");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_instance_translates_fluent() {
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  i18n.load_translations_from_text("en-US", "hi = Hi, { $name }!").unwrap();
+  i18n.set_language("en-US");
+  let mut args = egui_i18n::fluent::FluentArgs::new();
+  args.set("name", "Ada");
+  assert_eq!(i18n.translate_fluent("hi", &args), "Hi, Ada!");
+}
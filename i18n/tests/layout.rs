@@ -0,0 +1,204 @@
+//! Integration tests for loading directory trees by `Layout`.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use egui_i18n::{Args, FileSource, I18n, Layout, TranslationSource};
+
+fn tr(i18n: &I18n, language: &str, key: &str) -> String {
+  i18n.set_language(language);
+  i18n.translate(key, &Args::new())
+}
+
+/// The files of `source` relative to `dir`, with their languages.
+fn files(dir: &Path, source: &FileSource) -> Vec<(String, String)> {
+  let entries = source.entries().unwrap();
+  entries
+    .into_iter()
+    .map(|entry| {
+      let path = Path::new(entry.id.as_ref()).strip_prefix(dir).unwrap();
+      let path = path.with_extension("").to_string_lossy().replace('\\', "/");
+      (path, entry.language.into_owned())
+    })
+    .collect()
+}
+
+fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+  pairs.iter().map(|(path, language)| (path.to_string(), language.to_string())).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Layouts
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_flat_ignores_subdirectories() {
+  let dir = common::temp_dir("layout", "flat");
+  common::write(&dir, "en-US", "title = Title");
+  common::write(&dir, "de-DE/main", "title = Titel");
+
+  let source = FileSource::new(&dir);
+  assert_eq!(files(&dir, &source), pairs(&[("en-US", "en-US")]));
+  let i18n = I18n::new();
+  i18n.load_translations_from_path_with_layout(&dir, Layout::Flat).unwrap();
+  assert_eq!(i18n.languages(), ["en-US"]);
+}
+
+#[test]
+fn test_language_dirs() {
+  let dir = common::temp_dir("layout", "language-dirs");
+  common::write(&dir, "en-US/main", "title = Title");
+  common::write(&dir, "en-US/settings", "theme = Theme");
+  common::write(&dir, "en-US/settings/advanced", "cache = Cache");
+  common::write(&dir, "de-DE/main", "title = Titel");
+  fs::write(dir.join("en-US").join("notes.txt"), "not a catalog").unwrap();
+
+  let source = FileSource::new(&dir).layout(Layout::LanguageDirs);
+  assert_eq!(
+    files(&dir, &source),
+    pairs(&[
+      ("de-DE/main", "de-DE"),
+      ("en-US/main", "en-US"),
+      // Sorted by path component, so a directory before the file it shares
+      // a name with.
+      ("en-US/settings/advanced", "en-US"),
+      ("en-US/settings", "en-US"),
+    ])
+  );
+
+  let i18n = I18n::new();
+  i18n.load_translations_from_path_with_layout(&dir, Layout::LanguageDirs).unwrap();
+  assert_eq!(tr(&i18n, "en-US", "title"), "Title");
+  assert_eq!(tr(&i18n, "en-US", "theme"), "Theme");
+  assert_eq!(tr(&i18n, "en-US", "cache"), "Cache");
+  assert_eq!(tr(&i18n, "de-DE", "title"), "Titel");
+}
+
+#[test]
+fn test_domain_dirs() {
+  let dir = common::temp_dir("layout", "domain-dirs");
+  common::write(&dir, "en-US", "title = Title");
+  common::write(&dir, "de-DE", "title = Titel");
+  common::write(&dir, "plugin-x/en-US", "title = Plugin title");
+  common::write(&dir, "plugins/export/en-US", "export = Export");
+
+  let source = FileSource::new(&dir).layout(Layout::DomainDirs);
+  assert_eq!(
+    files(&dir, &source),
+    pairs(&[
+      ("de-DE", "de-DE"),
+      ("en-US", "en-US"),
+      ("plugin-x/en-US", "en-US"),
+      ("plugins/export/en-US", "en-US")
+    ])
+  );
+  let domains: Vec<String> =
+    source.entries().unwrap().into_iter().map(|entry| entry.domain.into_owned()).collect();
+  assert_eq!(domains, ["", "", "plugin-x", "plugins/export"]);
+
+  let i18n = I18n::new();
+  i18n.load_from_source(&source).unwrap();
+  assert_eq!(i18n.domains(), ["plugin-x", "plugins/export"]);
+  assert_eq!(tr(&i18n, "en-US", "title"), "Title");
+  assert_eq!(tr(&i18n, "de-DE", "title"), "Titel");
+  assert_eq!(tr(&i18n, "en-US", "export"), "");
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate_in("plugin-x", "title", &Args::new()), "Plugin title");
+  assert_eq!(i18n.translate_in("plugins/export", "export", &Args::new()), "Export");
+}
+
+#[test]
+fn test_domain_dirs_below_a_domain() {
+  let dir = common::temp_dir("layout", "domain-dirs-nested");
+  common::write(&dir, "en-US", "title = Plugin");
+  common::write(&dir, "export/en-US", "export = Export");
+
+  let i18n = I18n::new();
+  let source = FileSource::new(&dir).layout(Layout::DomainDirs);
+  i18n.load_domain_from_source("plugin-x", &source).unwrap();
+  assert_eq!(i18n.domains(), ["plugin-x", "plugin-x/export"]);
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate_in("plugin-x/export", "export", &Args::new()), "Export");
+}
+
+#[test]
+fn test_root_files_are_named_by_stem() {
+  let dir = common::temp_dir("layout", "root");
+  common::write(&dir, "fr-FR", "title = Titre");
+  common::write(&dir, "en-US/main", "title = Title");
+
+  let source = FileSource::new(&dir).layout(Layout::LanguageDirs);
+  assert_eq!(files(&dir, &source), pairs(&[("en-US/main", "en-US"), ("fr-FR", "fr-FR")]));
+}
+
+#[test]
+fn test_hidden_directories_are_skipped() {
+  let dir = common::temp_dir("layout", "hidden");
+  common::write(&dir, "en-US/main", "title = Title");
+  common::write(&dir, "en-US/.backup/main", "title = Old");
+  common::write(&dir, ".git/de-DE", "title = Titel");
+
+  let source = FileSource::new(&dir).layout(Layout::LanguageDirs);
+  assert_eq!(files(&dir, &source), pairs(&[("en-US/main", "en-US")]));
+  let source = FileSource::new(&dir).layout(Layout::DomainDirs);
+  assert_eq!(files(&dir, &source), pairs(&[("en-US/main", "main")]));
+}
+
+#[test]
+fn test_single_file_ignores_layout() {
+  let dir = common::temp_dir("layout", "single");
+  let file = common::write(&dir, "en-US/main", "title = Title");
+
+  let source = FileSource::new(&file).layout(Layout::LanguageDirs);
+  assert_eq!(files(&dir, &source), pairs(&[("en-US/main", "main")]));
+}
+
+#[test]
+fn test_global_load_with_layout() {
+  let dir = common::temp_dir("layout", "global");
+  // A language tag no other test loads.
+  common::write(&dir, "tlh-Latn/main", "title = Global");
+  egui_i18n::load_translations_from_path_with_layout(&dir, Layout::LanguageDirs).unwrap();
+  assert!(egui_i18n::languages().contains(&"tlh-Latn".to_string()));
+}
+
+// ---------------------------------------------------------------------------
+// Classic
+// ---------------------------------------------------------------------------
+
+#[cfg(not(feature = "fluent"))]
+#[test]
+fn test_classic_files_add_up_and_later_wins() {
+  let dir = common::temp_dir("layout", "classic-merge");
+  common::write(&dir, "en-US/a", "title = First\nsave = Save");
+  common::write(&dir, "en-US/b", "title = Second");
+
+  let i18n = I18n::new();
+  i18n.load_translations_from_path_with_layout(&dir, Layout::LanguageDirs).unwrap();
+  assert_eq!(tr(&i18n, "en-US", "title"), "Second");
+  assert_eq!(tr(&i18n, "en-US", "save"), "Save");
+
+  // Loading text for the language replaces neither file.
+  i18n.load_translations_from_text("en-US", "open = Open").unwrap();
+  assert_eq!(tr(&i18n, "en-US", "open"), "Open");
+  assert_eq!(tr(&i18n, "en-US", "save"), "Save");
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_language_dir_files_are_fluent_resources() {
+  let dir = common::temp_dir("layout", "fluent-resources");
+  let main = common::write(&dir, "en-US/main", "title = Title");
+  let settings = common::write(&dir, "en-US/settings", "theme = Theme");
+
+  let i18n = I18n::new();
+  i18n.load_translations_from_path_with_layout(&dir, Layout::LanguageDirs).unwrap();
+  let ids = [main.to_string_lossy().to_string(), settings.to_string_lossy().to_string()];
+  assert_eq!(i18n.resources("en-US"), ids);
+}
@@ -0,0 +1,124 @@
+//! Integration tests for BCP-47 normalization and the negotiated fallback
+//! chain.
+
+use egui_i18n::locale::{expand, fallback_chain, normalize};
+use egui_i18n::{Args, I18n};
+
+// ---------------------------------------------------------------------------
+// normalize / expand / fallback_chain
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_normalize_underscore_and_case() {
+  assert_eq!(normalize("en_US"), "en-US");
+  assert_eq!(normalize("en-us"), "en-US");
+  assert_eq!(normalize("zh_hant_tw"), "zh-Hant-TW");
+}
+
+#[test]
+fn test_normalize_keeps_malformed_tags() {
+  assert_eq!(normalize("cl-int-whatever"), "cl-int-whatever");
+}
+
+#[test]
+fn test_expand_drops_subtags_in_order() {
+  assert_eq!(expand("zh-Hant-TW"), ["zh-Hant-TW", "zh-Hant", "zh"]);
+  assert_eq!(expand("en-US-posix"), ["en-US-posix", "en-US", "en"]);
+  assert_eq!(expand("en_US"), ["en-US", "en"]);
+  assert_eq!(expand("en"), ["en"]);
+}
+
+#[test]
+fn test_expand_empty_and_malformed() {
+  assert!(expand("").is_empty());
+  assert_eq!(expand("not a tag"), ["not a tag"]);
+}
+
+#[test]
+fn test_fallback_chain_appends_fallbacks_without_duplicates() {
+  assert_eq!(
+    fallback_chain("zh-Hant-TW", &["en-US", "zh"]),
+    ["zh-Hant-TW", "zh-Hant", "zh", "en-US", "en"]
+  );
+}
+
+#[test]
+fn test_fallback_chain_with_empty_language() {
+  assert_eq!(fallback_chain("", &["en-US"]), ["en-US", "en"]);
+  assert!(fallback_chain("", &[] as &[&str]).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// I18n configuration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_language_chain_reflects_configuration() {
+  let i18n = I18n::new();
+  i18n.set_language("zh-Hant-TW");
+  i18n.set_fallbacks(&["en-US"]);
+  assert_eq!(i18n.language_chain(), ["zh-Hant-TW", "zh-Hant", "zh", "en-US", "en"]);
+}
+
+#[test]
+fn test_set_fallback_replaces_list() {
+  let i18n = I18n::new();
+  i18n.set_fallbacks(&["en-US", "fr-FR"]);
+  assert_eq!(i18n.get_fallback(), "en-US");
+  i18n.set_fallback("de-DE");
+  assert_eq!(i18n.get_fallbacks(), ["de-DE"]);
+  i18n.set_fallback("");
+  assert!(i18n.get_fallbacks().is_empty());
+  assert_eq!(i18n.get_fallback(), "");
+}
+
+// ---------------------------------------------------------------------------
+// Lookups through the chain
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_lookup_walks_to_less_specific_tag() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("zh-Hant", "msg = hant").unwrap();
+  i18n.load_translations_from_text("zh", "msg = zh\nonly-zh = zh only").unwrap();
+  i18n.set_language("zh-Hant-TW");
+  assert_eq!(i18n.translate("msg", &Args::new()), "hant");
+  assert_eq!(i18n.translate("only-zh", &Args::new()), "zh only");
+}
+
+#[test]
+fn test_lookup_uses_fallback_list_in_order() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("fr-FR", "a = fr a").unwrap();
+  i18n.load_translations_from_text("en-US", "a = en a\nb = en b").unwrap();
+  i18n.set_language("ja-JP");
+  i18n.set_fallbacks(&["fr-FR", "en-US"]);
+  assert_eq!(i18n.translate("a", &Args::new()), "fr a");
+  assert_eq!(i18n.translate("b", &Args::new()), "en b");
+}
+
+#[test]
+fn test_lookup_matches_underscore_catalog_names() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("en_US", "msg = underscore").unwrap();
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate("msg", &Args::new()), "underscore");
+  assert_eq!(i18n.languages(), ["en_US"]);
+}
+
+#[test]
+fn test_lookup_with_underscore_language() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("pt-BR", "msg = brasil").unwrap();
+  i18n.set_language("pt_br");
+  assert_eq!(i18n.translate("msg", &Args::new()), "brasil");
+}
+
+#[test]
+fn test_region_fallback_reaches_fallback_language_base() {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("en", "msg = english").unwrap();
+  i18n.set_language("ja-JP");
+  i18n.set_fallbacks(&["en-GB"]);
+  assert_eq!(i18n.translate("msg", &Args::new()), "english");
+}
@@ -0,0 +1,197 @@
+//! Integration tests for the missing-key policy and the process-wide record
+//! of missing keys.
+//!
+//! The record is shared by every translator in the process, so every test
+//! holds `SERIAL` and clears it first.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use egui_i18n::{tr, Args, Format, I18n, MissingKey, MissingKeyPolicy};
+
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn instance() -> I18n {
+  let i18n = common::instance(Format::default(), "present = Here");
+  i18n.load_translations_from_text("de-DE", "only-de = Nur hier").unwrap();
+  i18n.set_language("de-DE");
+  i18n.set_fallback("en-US");
+  i18n
+}
+
+fn temp_file(name: &str) -> std::path::PathBuf {
+  std::env::temp_dir().join(format!("egui-i18n-missing-{}-{}", name, std::process::id()))
+}
+
+// ---------------------------------------------------------------------------
+// Policies
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_default_policy_is_empty() {
+  let _g = SERIAL.lock().unwrap();
+  let i18n = instance();
+  assert!(matches!(i18n.get_missing_key_policy(), MissingKeyPolicy::Empty));
+  assert_eq!(i18n.translate("absent", &Args::new()), "");
+}
+
+#[test]
+fn test_key_policy() {
+  let _g = SERIAL.lock().unwrap();
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(i18n.translate("absent", &Args::new()), "absent");
+  assert_eq!(i18n.translate("present", &Args::new()), "Here");
+}
+
+#[test]
+fn test_marker_policy() {
+  let _g = SERIAL.lock().unwrap();
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::Marker);
+  assert_eq!(i18n.translate("absent", &Args::new()), "⟦absent⟧");
+}
+
+#[test]
+fn test_custom_policy_receives_language_and_chain() {
+  let _g = SERIAL.lock().unwrap();
+  let seen: Arc<Mutex<Vec<MissingKey>>> = Arc::default();
+  let sink = seen.clone();
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::custom(move |missing| {
+    sink.lock().unwrap().push(missing.clone());
+    format!("TODO({})", missing.key)
+  }));
+  assert_eq!(i18n.translate("absent", &Args::new()), "TODO(absent)");
+
+  let seen = seen.lock().unwrap();
+  assert_eq!(seen.len(), 1);
+  assert_eq!(seen[0].key, "absent");
+  assert_eq!(seen[0].language, "de-DE");
+  assert_eq!(seen[0].chain, ["de-DE", "de", "en-US", "en"]);
+}
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_policy_applies_to_translate_classic() {
+  let _g = SERIAL.lock().unwrap();
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(i18n.translate_classic("absent", &Default::default()), "absent");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_policy_applies_to_translate_fluent_and_try_translate() {
+  let _g = SERIAL.lock().unwrap();
+  let i18n = instance();
+  i18n.set_missing_key_policy(MissingKeyPolicy::Marker);
+  assert_eq!(i18n.translate_fluent("absent", &Default::default()), "⟦absent⟧");
+  assert_eq!(i18n.try_translate("absent", &Args::new()), ("⟦absent⟧".to_string(), vec![]));
+}
+
+#[test]
+fn test_tr_uses_global_policy() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::set_missing_key_policy(MissingKeyPolicy::Marker);
+  assert_eq!(tr!("missing-globally"), "⟦missing-globally⟧");
+  egui_i18n::set_missing_key_policy(MissingKeyPolicy::Empty);
+  assert_eq!(tr!("missing-globally"), "");
+}
+
+// ---------------------------------------------------------------------------
+// Record
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_record_is_shared_and_deduplicated() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::clear_missing_keys();
+  let a = instance();
+  let b = I18n::new();
+  b.set_language("fr-FR");
+
+  a.translate("absent", &Args::new());
+  a.translate("absent", &Args::new());
+  a.translate("present", &Args::new());
+  a.translate("only-de", &Args::new());
+  b.translate("absent", &Args::new());
+
+  let missing = egui_i18n::missing_keys();
+  let entries: Vec<(&str, &str)> =
+    missing.iter().map(|m| (m.language.as_str(), m.key.as_str())).collect();
+  assert_eq!(entries, [("de-DE", "absent"), ("fr-FR", "absent")]);
+
+  egui_i18n::clear_missing_keys();
+  assert!(egui_i18n::missing_keys().is_empty());
+}
+
+#[test]
+fn test_dump_missing_keys() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::clear_missing_keys();
+  let i18n = instance();
+  i18n.translate("zeta", &Args::new());
+  i18n.translate("alpha", &Args::new());
+  i18n.set_language("en-US");
+  i18n.translate("beta", &Args::new());
+
+  let file = temp_file("dump");
+  egui_i18n::dump_missing_keys(&file).unwrap();
+  let content = std::fs::read_to_string(&file).unwrap();
+  std::fs::remove_file(&file).unwrap();
+  egui_i18n::clear_missing_keys();
+
+  assert_eq!(content, "# de-DE\nalpha =\nzeta =\n\n# en-US\nbeta =\n");
+}
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_dumped_keys_parse_back() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::clear_missing_keys();
+  let i18n = instance();
+  let keys = ["# Open", "a = b", "[x", " padded ", "back\\slash"];
+  for key in keys {
+    i18n.translate(key, &Args::new());
+  }
+  i18n.translate_ctx("menu", "# Open", &Args::new());
+
+  let file = temp_file("dump-escaped");
+  egui_i18n::dump_missing_keys(&file).unwrap();
+  let content = std::fs::read_to_string(&file).unwrap();
+  std::fs::remove_file(&file).unwrap();
+  egui_i18n::clear_missing_keys();
+
+  let parsed = egui_i18n::parse_translations(&content, false);
+  let mut parsed: Vec<&str> = parsed.keys().map(String::as_str).collect();
+  parsed.sort();
+  let context_key = egui_i18n::context_key("menu", "# Open");
+  let mut expected = keys.to_vec();
+  expected.push(&context_key);
+  expected.sort();
+  assert_eq!(parsed, expected);
+}
+
+#[test]
+fn test_domain_misses_are_recorded_per_domain() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::clear_missing_keys();
+  let i18n = instance();
+  i18n.translate_in("plugin-x", "export", &Args::new());
+  i18n.translate("export", &Args::new());
+  // Found in the core domain, so not missing.
+  i18n.translate_in("plugin-x", "only-de", &Args::new());
+
+  let domains: Vec<String> = egui_i18n::missing_keys().into_iter().map(|m| m.domain).collect();
+  assert_eq!(domains, ["", "plugin-x"]);
+
+  let file = temp_file("dump-domain");
+  egui_i18n::dump_missing_keys(&file).unwrap();
+  let content = std::fs::read_to_string(&file).unwrap();
+  std::fs::remove_file(&file).unwrap();
+  egui_i18n::clear_missing_keys();
+
+  assert_eq!(content, "# de-DE\nexport =\n\n# de-DE (plugin-x)\nexport =\n");
+}
@@ -0,0 +1,242 @@
+//! Integration tests for builds with both the `classic` and the `fluent`
+//! backend compiled in.
+//!
+//! Most tests work on their own `I18n::new()` value. The `tr!` tests go
+//! through the process-wide default instance, so they hold a shared `Mutex`
+//! guard while they set the global language and fallback.
+
+#![cfg(all(feature = "classic", feature = "fluent"))]
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use egui_i18n::{Args, Format, I18n};
+
+// Guard used by tests that set / read the global language and fallback state.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn instance(language: &str, fallback: &str) -> I18n {
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  i18n.set_language(language);
+  i18n.set_fallback(fallback);
+  i18n
+}
+
+fn args<'a>(pairs: &[(&'a str, &'a str)]) -> Args<'a> {
+  let mut args = Args::new();
+  for (name, value) in pairs {
+    args.set(name, *value);
+  }
+  args
+}
+
+// ---------------------------------------------------------------------------
+// Format
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_format_from_extension() {
+  assert_eq!(Format::from_extension("egl"), Some(Format::Classic));
+  assert_eq!(Format::from_extension("FTL"), Some(Format::Fluent));
+  assert_eq!(Format::from_extension("txt"), None);
+}
+
+#[test]
+fn test_default_format_is_fluent() {
+  assert_eq!(Format::default(), Format::Fluent);
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_load_with_format_routes_to_backend() {
+  let i18n = instance("en-US", "");
+  i18n
+    .load_translations_from_text_with_format("en-US", "hi = Hi, {name}!", Format::Classic)
+    .unwrap();
+  i18n
+    .load_translations_from_text_with_format("de-DE", "hi = Hallo, { $name }!", Format::Fluent)
+    .unwrap();
+
+  let mut classic_args = HashMap::new();
+  classic_args.insert("name", "Ada".to_string());
+  assert_eq!(i18n.translate_classic("hi", &classic_args), "Hi, Ada!");
+
+  let mut languages = i18n.languages();
+  languages.sort();
+  assert_eq!(languages, vec!["de-DE".to_string(), "en-US".to_string()]);
+}
+
+#[test]
+fn test_load_from_map_works_alongside_fluent() {
+  let i18n = instance("en-US", "");
+  let mut map = HashMap::new();
+  map.insert("key".to_string(), "value".to_string());
+  i18n.load_translations_from_map("en-US", map).unwrap();
+  assert_eq!(i18n.translate("key", &Args::new()), "value");
+}
+
+#[test]
+fn test_language_in_both_backends_listed_once() {
+  let i18n = instance("en-US", "");
+  i18n.load_translations_from_text_with_format("en-US", "a = A", Format::Classic).unwrap();
+  i18n.load_translations_from_text_with_format("en-US", "b = B", Format::Fluent).unwrap();
+  assert_eq!(i18n.languages(), vec!["en-US".to_string()]);
+}
+
+#[test]
+fn test_load_from_path_routes_by_extension() {
+  let dir = std::env::temp_dir().join(format!("egui-i18n-mixed-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("en-US.egl"), "Hello\\=, {name}! = Hello=, {name}!").unwrap();
+  std::fs::write(dir.join("en-US.ftl"), "hello-name = Hello, { $name }!").unwrap();
+
+  let i18n = instance("en-US", "");
+  i18n.load_translations_from_path(dir.to_string_lossy()).unwrap();
+  std::fs::remove_dir_all(&dir).unwrap();
+
+  let args = args(&[("name", "Ada")]);
+  assert_eq!(i18n.translate("Hello=, {name}!", &args), "Hello=, Ada!");
+  assert_eq!(i18n.translate("hello-name", &args), "Hello, Ada!");
+}
+
+// ---------------------------------------------------------------------------
+// Lookup order
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_fluent_wins_over_classic_for_same_language() {
+  let i18n = instance("en-US", "");
+  i18n.load_translations_from_text_with_format("en-US", "msg = classic", Format::Classic).unwrap();
+  i18n.load_translations_from_text_with_format("en-US", "msg = fluent", Format::Fluent).unwrap();
+  assert_eq!(i18n.translate("msg", &Args::new()), "fluent");
+}
+
+#[test]
+fn test_classic_used_when_fluent_lacks_key() {
+  let i18n = instance("en-US", "");
+  i18n.load_translations_from_text_with_format("en-US", "old = from egl", Format::Classic).unwrap();
+  i18n.load_translations_from_text_with_format("en-US", "new = from ftl", Format::Fluent).unwrap();
+  assert_eq!(i18n.translate("old", &Args::new()), "from egl");
+  assert_eq!(i18n.translate("new", &Args::new()), "from ftl");
+}
+
+#[test]
+fn test_language_checked_in_both_backends_before_fallback() {
+  let i18n = instance("ja-JP", "en-US");
+  i18n
+    .load_translations_from_text_with_format("ja-JP", "msg = ja classic", Format::Classic)
+    .unwrap();
+  i18n.load_translations_from_text_with_format("en-US", "msg = en fluent", Format::Fluent).unwrap();
+  assert_eq!(i18n.translate("msg", &Args::new()), "ja classic");
+}
+
+#[test]
+fn test_fallback_checked_in_both_backends() {
+  let i18n = instance("ja-JP", "en-US");
+  i18n
+    .load_translations_from_text_with_format("en-US", "msg = en classic", Format::Classic)
+    .unwrap();
+  assert_eq!(i18n.translate("msg", &Args::new()), "en classic");
+}
+
+#[test]
+fn test_missing_everywhere_returns_empty() {
+  let i18n = instance("ja-JP", "en-US");
+  i18n.load_translations_from_text_with_format("en-US", "a = A", Format::Classic).unwrap();
+  i18n.load_translations_from_text_with_format("en-US", "b = B", Format::Fluent).unwrap();
+  assert_eq!(i18n.translate("c", &Args::new()), "");
+}
+
+// ---------------------------------------------------------------------------
+// tr! macro
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_tr_macro_reaches_both_backends() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::load_translations_from_text_with_format(
+    "mt-MT",
+    "My name is {name} and {age} years old = I am {name}, {age}.",
+    Format::Classic,
+  )
+  .unwrap();
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_text_with_format(
+    "mt-MT",
+    "my-name-and-age = I am { $name }, { $age }.",
+    Format::Fluent,
+  )
+  .unwrap();
+  egui_i18n::set_use_isolating(true);
+  egui_i18n::set_language("mt-MT");
+  egui_i18n::set_fallback("mt-MT");
+
+  let name = "Ada".to_string();
+  let age = 36_u32;
+  assert_eq!(
+    egui_i18n::tr!("My name is {name} and {age} years old", { name: &name, age: age }),
+    "I am Ada, 36."
+  );
+  assert_eq!(egui_i18n::tr!("my-name-and-age", { name: &name, age: age }), "I am Ada, 36.");
+}
+
+#[test]
+fn test_tr_macro_takes_fluent_values() {
+  let _g = SERIAL.lock().unwrap();
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_text_with_format("mt-MT", "count = { $n }", Format::Fluent)
+    .unwrap();
+  egui_i18n::set_use_isolating(true);
+  egui_i18n::set_language("mt-MT");
+
+  let n = egui_i18n::fluent::FluentValue::from(3);
+  assert_eq!(egui_i18n::tr!("count", { n: n }), "3");
+}
+
+// ---------------------------------------------------------------------------
+// Fluent values
+// ---------------------------------------------------------------------------
+
+fn two_decimals(value: f64) -> egui_i18n::fluent_bundle::types::FluentNumber {
+  use egui_i18n::fluent_bundle::types::{FluentNumber, FluentNumberOptions};
+
+  let options = FluentNumberOptions { minimum_fraction_digits: Some(2), ..Default::default() };
+  FluentNumber::new(value, options)
+}
+
+#[test]
+fn test_fluent_values_pass_through_to_fluent() {
+  let i18n = instance("en-US", "");
+  i18n.load_translations_from_text_with_format("en-US", "v = { $v }", Format::Fluent).unwrap();
+  let mut args = Args::new();
+  args.set("v", egui_i18n::fluent::FluentValue::from("x"));
+  assert_eq!(i18n.translate("v", &args), "x");
+
+  let mut args = Args::new();
+  args.set("v", two_decimals(3.0));
+  assert_eq!(i18n.translate("v", &args), "3.00");
+}
+
+#[test]
+fn test_fluent_values_in_classic_catalogs() {
+  let i18n = instance("en-US", "");
+  i18n
+    .load_translations_from_text_with_format("en-US", "v = <{v}>\nn = {v:number}", Format::Classic)
+    .unwrap();
+  let mut args = Args::new();
+  args.set("v", egui_i18n::fluent::FluentValue::from("x"));
+  assert_eq!(i18n.translate("v", &args), "<x>");
+
+  let mut args = Args::new();
+  args.set("v", two_decimals(1234.0));
+  assert_eq!(i18n.translate("v", &args), "<1234.00>");
+  assert_eq!(i18n.translate("n", &args), "1,234");
+}
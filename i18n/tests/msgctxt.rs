@@ -0,0 +1,194 @@
+//! Integration tests for message contexts (`[menu] Open = …`).
+
+use egui_i18n::{context_key, escape_key, parse_translations, split_context_key};
+
+// ---------------------------------------------------------------------------
+// Catalog format
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_context_keys() {
+  let key = context_key("menu", "Open");
+  assert_eq!(split_context_key(&key), Some(("menu", "Open")));
+  assert_eq!(split_context_key("Open"), None);
+  assert_ne!(key, context_key("status", "Open"));
+}
+
+#[test]
+fn test_bracketed_context_is_parsed() {
+  let map = parse_translations(
+    "Open = Öffnen\n[menu] Open = Öffnen…\n[ status ]\tOpen = Geöffnet\n[] Open = Leer",
+    true,
+  );
+  assert_eq!(map.len(), 4);
+  assert_eq!(map["Open"], "Öffnen");
+  assert_eq!(map[&context_key("menu", "Open")], "Öffnen…");
+  assert_eq!(map[&context_key("status", "Open")], "Geöffnet");
+  assert_eq!(map[&context_key("", "Open")], "Leer");
+}
+
+#[test]
+fn test_escaped_or_unclosed_bracket_is_part_of_the_key() {
+  let map = parse_translations("\\[draft] Open = a\n[draft Open = b\n[a\\]b] c = d", true);
+  assert_eq!(map["[draft] Open"], "a");
+  assert_eq!(map["[draft Open"], "b");
+  assert_eq!(map[&context_key("a]b", "c")], "d");
+}
+
+#[test]
+fn test_brackets_without_a_key_after_them_are_part_of_the_key() {
+  let map = parse_translations("[beta]label = a\n[beta] = b", true);
+  assert_eq!(map.len(), 2);
+  assert_eq!(map["[beta]label"], "a");
+  assert_eq!(map["[beta]"], "b");
+}
+
+#[test]
+fn test_context_key_round_trip() {
+  for key in [
+    context_key("menu", "Open"),
+    context_key("a = b", "c = d"),
+    context_key("[x]", "#y"),
+    context_key(" padded ", " key "),
+    "[not a context] key".to_string(),
+  ] {
+    let content = format!("{} = value", escape_key(&key));
+    let map = parse_translations(&content, true);
+    assert_eq!(map.keys().collect::<Vec<_>>(), [&key], "{content:?}");
+  }
+  assert_eq!(escape_key(&context_key("menu", "Open")), "[menu] Open");
+}
+
+// ---------------------------------------------------------------------------
+// Translating
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "classic")]
+mod classic {
+  use egui_i18n::{tr, Args, Format, I18n, MemorySource, MissingKeyPolicy};
+
+  const EGL: &str = "
+Open = Öffnen
+[menu] Open = Öffnen…
+[status] Open = Geöffnet
+[menu] Save = {name} speichern
+";
+
+  fn instance() -> I18n {
+    let i18n = I18n::new();
+    i18n.load_translations_from_text_with_format("de-DE", EGL, Format::Classic).unwrap();
+    i18n.set_language("de-DE");
+    i18n
+  }
+
+  #[test]
+  fn test_each_context_has_its_translation() {
+    let i18n = instance();
+    assert_eq!(i18n.translate_ctx("menu", "Open", &Args::new()), "Öffnen…");
+    assert_eq!(i18n.translate_ctx("status", "Open", &Args::new()), "Geöffnet");
+    assert_eq!(i18n.translate("Open", &Args::new()), "Öffnen");
+  }
+
+  #[test]
+  fn test_context_with_args() {
+    let i18n = instance();
+    let mut args = Args::new();
+    args.set("name", "Notizen");
+    assert_eq!(i18n.translate_ctx("menu", "Save", &args), "Notizen speichern");
+  }
+
+  #[test]
+  fn test_unknown_context_falls_back_to_the_key() {
+    let i18n = instance();
+    assert_eq!(i18n.translate_ctx("toolbar", "Open", &Args::new()), "Öffnen");
+  }
+
+  #[test]
+  fn test_missing_context_key_is_the_plain_key() {
+    let i18n = instance();
+    i18n.set_missing_key_policy(MissingKeyPolicy::Key);
+    assert_eq!(i18n.translate_ctx("menu", "Close", &Args::new()), "Close");
+  }
+
+  #[test]
+  fn test_missing_context_key_is_recorded_with_its_context() {
+    let i18n = instance();
+    i18n.translate_ctx("menu", "# Not translated", &Args::new());
+    let keys: Vec<String> = egui_i18n::missing_keys().into_iter().map(|m| m.key).collect();
+    assert!(keys.contains(&egui_i18n::context_key("menu", "# Not translated")));
+    assert!(!keys.contains(&"# Not translated".to_string()));
+    // Found without its context, so nothing is missing.
+    i18n.translate_ctx("menu", "Save", &Args::new());
+    i18n.translate_ctx("toolbar", "Open", &Args::new());
+    let keys: Vec<String> = egui_i18n::missing_keys().into_iter().map(|m| m.key).collect();
+    assert!(!keys.iter().any(|key| key.ends_with("Open") || key.ends_with("Save")));
+  }
+
+  #[test]
+  fn test_fallback_language_context_wins_over_plain_key() {
+    let i18n = instance();
+    i18n
+      .load_translations_from_text_with_format("fr-FR", "Open = Ouvrir", Format::Classic)
+      .unwrap();
+    i18n.set_language("fr-FR");
+    i18n.set_fallback("de-DE");
+    // The whole chain is searched for the context before the plain key.
+    assert_eq!(i18n.translate_ctx("menu", "Open", &Args::new()), "Öffnen…");
+    assert_eq!(i18n.translate_ctx("toolbar", "Open", &Args::new()), "Ouvrir");
+  }
+
+  #[test]
+  fn test_cache_keeps_contexts_apart() {
+    let i18n = instance();
+    i18n.set_cache_enabled(true);
+    for _ in 0..2 {
+      assert_eq!(i18n.translate_ctx("menu", "Open", &Args::new()), "Öffnen…");
+      assert_eq!(i18n.translate_ctx("status", "Open", &Args::new()), "Geöffnet");
+      assert_eq!(i18n.translate("Open", &Args::new()), "Öffnen");
+    }
+    assert_eq!(i18n.cache_stats().hits, 3);
+  }
+
+  #[test]
+  fn test_context_in_a_domain() {
+    let i18n = instance();
+    let plugin = "[menu] Open = Plugin öffnen";
+    let source = MemorySource::new().add("de-DE", Format::Classic, plugin);
+    i18n.load_domain_from_source("plugin-x", &source).unwrap();
+    let key = egui_i18n::context_key("menu", "Open");
+    assert_eq!(i18n.translate_in("plugin-x", &key, &Args::new()), "Plugin öffnen");
+    let key = egui_i18n::context_key("status", "Open");
+    assert_eq!(i18n.translate_in("plugin-x", &key, &Args::new()), "Geöffnet");
+  }
+
+  #[test]
+  fn test_global_macro() {
+    // A language tag and domain no other test loads.
+    egui_i18n::load_translations_from_text_with_format("tlh-Latn", EGL, Format::Classic).unwrap();
+    let plugin = "[menu] Open = Plugin {name}";
+    let source = MemorySource::new().add("tlh-Latn", Format::Classic, plugin);
+    egui_i18n::load_domain_from_source("tlh-context", &source).unwrap();
+    egui_i18n::set_language("tlh-Latn");
+
+    assert_eq!(tr!(ctx: "menu", "Open"), "Öffnen…");
+    assert_eq!(tr!(ctx: "menu", "Save", { name: "Notizen" }), "Notizen speichern");
+    assert_eq!(egui_i18n::translate_ctx("status", "Open", &Args::new()), "Geöffnet");
+    assert_eq!(tr!(domain = "tlh-context", ctx: "menu", "Open", { name: "x" }), "Plugin x");
+    assert_eq!(tr!(domain = "tlh-context", ctx: "status", "Open"), "Geöffnet");
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_fluent_messages_have_no_context() {
+  use egui_i18n::{Args, Format, I18n};
+
+  let i18n = I18n::new();
+  i18n.load_translations_from_text_with_format("en-US", "open = Open", Format::Fluent).unwrap();
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate_ctx("menu", "open", &Args::new()), "Open");
+}
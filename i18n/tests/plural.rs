@@ -0,0 +1,164 @@
+//! Integration tests for `plural`, `selectordinal` and `select` placeholders
+//! in the classic format.
+#![cfg(feature = "classic")]
+
+mod common;
+
+use std::collections::HashMap;
+
+use egui_i18n::{Format, I18n, I18nError, SyntaxError};
+
+const EGL: &str = "
+# Variants may share the key's line or sit on lines of their own.
+files = {count, plural, one {# file} other {# files}}
+inbox = {count, plural,
+    =0 {Your inbox is empty}
+    one {One message}
+    other {# messages}
+}
+place = You finished {rank, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}
+reply = {gender, select,
+    female {She replied}
+    male {He replied}
+    other {They replied}
+}
+nested = {gender, select,
+    female {{count, plural, one {She has # cat} other {She has # cats}}}
+    other {{count, plural, one {They have # cat} other {They have # cats}}}
+}
+after = Still parsed
+";
+
+/// Plural rules follow the language, so these tests pick it.
+fn instance_in(language: &str, content: &str) -> I18n {
+  let i18n = I18n::new();
+  i18n.load_translations_from_text_with_format(language, content, Format::Classic).unwrap();
+  i18n.set_language(language);
+  i18n
+}
+
+fn tr(i18n: &I18n, key: &str, pairs: &[(&'static str, &str)]) -> String {
+  let args: HashMap<&str, String> = pairs.iter().map(|(k, v)| (*k, v.to_string())).collect();
+  i18n.translate_classic(key, &args)
+}
+
+// ---------------------------------------------------------------------------
+// plural
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_plural_categories() {
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "files", &[("count", "1")]), "1 file");
+  assert_eq!(tr(&i18n, "files", &[("count", "5")]), "5 files");
+  // CLDR: visible fraction digits make English numbers `other`.
+  assert_eq!(tr(&i18n, "files", &[("count", "1.0")]), "1.0 files");
+}
+
+#[test]
+fn test_exact_match_wins() {
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "inbox", &[("count", "0")]), "Your inbox is empty");
+  assert_eq!(tr(&i18n, "inbox", &[("count", "1")]), "One message");
+  assert_eq!(tr(&i18n, "inbox", &[("count", "12")]), "12 messages");
+  assert_eq!(tr(&i18n, "after", &[]), "Still parsed");
+}
+
+#[test]
+fn test_rules_follow_the_language() {
+  let i18n = instance_in(
+    "pl-PL",
+    "files = {count, plural, one {# plik} few {# pliki} many {# plików} other {# pliku}}",
+  );
+  assert_eq!(tr(&i18n, "files", &[("count", "1")]), "1 plik");
+  assert_eq!(tr(&i18n, "files", &[("count", "3")]), "3 pliki");
+  assert_eq!(tr(&i18n, "files", &[("count", "5")]), "5 plików");
+  assert_eq!(tr(&i18n, "files", &[("count", "22")]), "22 pliki");
+  assert_eq!(tr(&i18n, "files", &[("count", "1.5")]), "1.5 pliku");
+
+  // Japanese has no plural forms.
+  let i18n = instance_in("ja-JP", "files = {count, plural, one {# one} other {# other}}");
+  assert_eq!(tr(&i18n, "files", &[("count", "1")]), "1 other");
+}
+
+#[test]
+fn test_unknown_language_and_bad_argument_use_other() {
+  let i18n = instance_in("xx-unknown", EGL);
+  assert_eq!(tr(&i18n, "files", &[("count", "1")]), "1 files");
+
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "files", &[("count", "many")]), "many files");
+  assert_eq!(tr(&i18n, "files", &[]), "{count} files");
+}
+
+#[test]
+fn test_selectordinal() {
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "place", &[("rank", "1")]), "You finished 1st");
+  assert_eq!(tr(&i18n, "place", &[("rank", "22")]), "You finished 22nd");
+  assert_eq!(tr(&i18n, "place", &[("rank", "23")]), "You finished 23rd");
+  assert_eq!(tr(&i18n, "place", &[("rank", "11")]), "You finished 11th");
+}
+
+// ---------------------------------------------------------------------------
+// select
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_select() {
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "reply", &[("gender", "female")]), "She replied");
+  assert_eq!(tr(&i18n, "reply", &[("gender", "male")]), "He replied");
+  assert_eq!(tr(&i18n, "reply", &[("gender", "robot")]), "They replied");
+  assert_eq!(tr(&i18n, "reply", &[]), "They replied");
+}
+
+#[test]
+fn test_nested() {
+  let i18n = common::instance(Format::Classic, EGL);
+  assert_eq!(tr(&i18n, "nested", &[("gender", "female"), ("count", "1")]), "She has 1 cat");
+  assert_eq!(tr(&i18n, "nested", &[("gender", "x"), ("count", "2")]), "They have 2 cats");
+}
+
+// ---------------------------------------------------------------------------
+// Plain text
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_other_braces_and_hashes_are_kept() {
+  let i18n = common::instance(
+    Format::Classic,
+    "a = Issue #{id} { not a placeholder } {x, unknown, one {1}}",
+  );
+  assert_eq!(
+    tr(&i18n, "a", &[("id", "7")]),
+    "Issue #7 { not a placeholder } {x, unknown, one {1}}"
+  );
+}
+
+// ---------------------------------------------------------------------------
+// Unclosed selectors
+// ---------------------------------------------------------------------------
+
+fn unclosed_at(content: &str) -> Vec<(usize, usize)> {
+  let error =
+    I18n::new().load_translations_from_text_with_format("en-US", content, Format::Classic);
+  let Err(I18nError::Parse { errors, .. }) = error else { panic!("expected Parse") };
+  assert!(errors.iter().all(|e| matches!(e.error, SyntaxError::UnclosedSelector)));
+  errors.iter().map(|e| (e.line, e.column)).collect()
+}
+
+#[test]
+fn test_unclosed_selector_ends_at_the_next_key() {
+  let egl = "a = ok\nb = Got {count, plural,\n  =0 {none}\n  one {# file}\nc = next";
+  assert_eq!(unclosed_at(egl), [(2, 9)]);
+  let map = egui_i18n::parse_translations(egl, true);
+  assert_eq!(map["c"], "next");
+  assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_unclosed_selector_at_end_of_file() {
+  assert_eq!(unclosed_at("a = ok\nb = {count, plural, one {# file}"), [(2, 5)]);
+  assert_eq!(unclosed_at("a = {n, select,\nb = {n, plural,"), [(1, 5), (2, 5)]);
+}
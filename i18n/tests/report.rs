@@ -0,0 +1,230 @@
+//! Integration tests for load reports and strict loading.
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use egui_i18n::{
+  Args, FileSource, I18n, I18nError, Layout, LoadMode, MemorySource, SourceEntry, TranslationSource,
+};
+
+/// Content that fails to parse with the backend compiled in.
+fn broken() -> &'static [u8] {
+  if cfg!(feature = "fluent") {
+    b"title = Broken\n!!!\n"
+  } else {
+    b"title = {count, plural, one {# item}\n"
+  }
+}
+
+/// `de-DE` is broken and sorts between the two good files.
+fn mixed_dir(name: &str) -> (PathBuf, [PathBuf; 3]) {
+  let dir = common::temp_dir("report", name);
+  let bad = common::write(&dir, "de-DE", broken());
+  let en = common::write(&dir, "en-US", "title = Title");
+  let fr = common::write(&dir, "fr-FR", "title = Titre");
+  (dir, [bad, en, fr])
+}
+
+fn title(i18n: &I18n, language: &str) -> String {
+  i18n.set_language(language);
+  i18n.translate("title", &Args::new())
+}
+
+fn languages(i18n: &I18n) -> Vec<String> {
+  let mut languages = i18n.languages();
+  languages.sort();
+  languages
+}
+
+// ---------------------------------------------------------------------------
+// Lenient
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_lenient_loads_the_others() {
+  let (dir, [bad, en, fr]) = mixed_dir("lenient");
+  let i18n = I18n::new();
+  let report = i18n.load_translations_from_path_with_report(&dir, LoadMode::Lenient);
+  assert!(!report.is_ok());
+  assert_eq!(report.loaded, [en, fr]);
+  assert_eq!(report.failed.len(), 1);
+  assert_eq!(report.failed[0].path(), Some(bad.as_path()));
+  assert_eq!(languages(&i18n), ["en-US", "fr-FR"]);
+  assert_eq!(title(&i18n, "fr-FR"), "Titre");
+}
+
+#[test]
+fn test_load_from_path_does_not_stop_at_a_broken_file() {
+  let (dir, [bad, ..]) = mixed_dir("path");
+  let i18n = I18n::new();
+  let error = i18n.load_translations_from_path(dir.to_string_lossy()).unwrap_err();
+  assert_eq!(error.path(), Some(bad.as_path()));
+  assert_eq!(languages(&i18n), ["en-US", "fr-FR"]);
+}
+
+#[test]
+fn test_every_failure_is_reported() {
+  let dir = common::temp_dir("report", "every");
+  let first = common::write(&dir, "de-DE", broken());
+  common::write(&dir, "en-US", "title = Title");
+  let second = common::write(&dir, "it-IT", broken());
+  let report = I18n::new().load_translations_from_path_with_report(&dir, LoadMode::Lenient);
+  let failed: Vec<Option<&Path>> = report.failed.iter().map(I18nError::path).collect();
+  assert_eq!(failed, [Some(first.as_path()), Some(second.as_path())]);
+  assert_eq!(report.loaded.len(), 1);
+}
+
+#[test]
+fn test_missing_directory_is_reported() {
+  let missing = std::env::temp_dir().join("egui-i18n-report-does-not-exist");
+  let report = I18n::new().load_translations_from_path_with_report(&missing, LoadMode::Lenient);
+  assert!(report.loaded.is_empty());
+  assert!(matches!(report.failed.as_slice(), [I18nError::Io { .. }]));
+  assert!(matches!(report.into_result(), Err(I18nError::Io { .. })));
+}
+
+#[test]
+fn test_clean_report() {
+  let dir = common::temp_dir("report", "clean");
+  let en = common::write(&dir, "en-US/main", "title = Title");
+  let source = FileSource::new(&dir).layout(Layout::LanguageDirs);
+  let report = I18n::new().load_from_source_with_report(&source, LoadMode::Lenient);
+  assert!(report.is_ok());
+  assert_eq!(report.loaded, [en]);
+  assert!(report.into_result().is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// Strict
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_strict_loads_nothing_on_failure() {
+  let (dir, [bad, ..]) = mixed_dir("strict");
+  let i18n = I18n::new();
+  let report = i18n.load_translations_from_path_with_report(&dir, LoadMode::Strict);
+  assert!(report.loaded.is_empty());
+  assert_eq!(report.failed.len(), 1);
+  assert_eq!(report.failed[0].path(), Some(bad.as_path()));
+  assert!(i18n.languages().is_empty());
+}
+
+#[test]
+fn test_strict_loads_everything_on_success() {
+  let dir = common::temp_dir("report", "strict-ok");
+  let en = common::write(&dir, "en-US", "title = Title");
+  let fr = common::write(&dir, "fr-FR", "title = Titre");
+  let i18n = I18n::new();
+  let report = i18n.load_translations_from_path_with_report(&dir, LoadMode::Strict);
+  assert!(report.is_ok());
+  assert_eq!(report.loaded, [en, fr]);
+  assert_eq!(title(&i18n, "en-US"), "Title");
+}
+
+#[test]
+fn test_strict_keeps_what_was_loaded_before() {
+  let (dir, _) = mixed_dir("strict-keep");
+  let i18n = I18n::new();
+  i18n.load_translations_from_text("en-US", "save = Save").unwrap();
+  let report = i18n.load_translations_from_path_with_report(&dir, LoadMode::Strict);
+  assert!(!report.is_ok());
+  assert_eq!(languages(&i18n), ["en-US"]);
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate("save", &Args::new()), "Save");
+  assert_eq!(i18n.translate("title", &Args::new()), "");
+}
+
+// ---------------------------------------------------------------------------
+// Sources
+// ---------------------------------------------------------------------------
+
+/// A source that cannot list its files at all.
+struct Unavailable;
+
+impl TranslationSource for Unavailable {
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    Err(I18nError::Io {
+      path: "bundle".into(),
+      source: std::io::Error::new(std::io::ErrorKind::NotFound, "no bundle"),
+    })
+  }
+}
+
+#[test]
+fn test_source_errors_are_reported() {
+  let report = I18n::new().load_from_source_with_report(&Unavailable, LoadMode::Lenient);
+  assert_eq!(report.failed.len(), 1);
+  assert_eq!(report.failed[0].path(), Some(Path::new("bundle")));
+}
+
+#[test]
+fn test_file_source_lists_readable_files() {
+  let (dir, [bad, ..]) = mixed_dir("entries");
+  let source = FileSource::new(&dir);
+  let (entries, errors) = source.entries_with_errors();
+  // Broken content still reads; it fails when loaded.
+  assert_eq!(entries.len(), 3);
+  assert!(errors.is_empty());
+  assert_eq!(entries[0].id, bad.to_string_lossy());
+}
+
+#[test]
+fn test_global_load_with_report() {
+  // A language tag no other test loads.
+  let source = MemorySource::new().add("tlh-Latn", Default::default(), "title = Global");
+  let report = egui_i18n::load_from_source_with_report(&source, LoadMode::Strict);
+  assert!(report.is_ok());
+  assert!(egui_i18n::languages().contains(&"tlh-Latn".to_string()));
+}
+
+// ---------------------------------------------------------------------------
+// Classic
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "classic")]
+#[test]
+fn test_strict_checks_classic_syntax() {
+  let source = MemorySource::new()
+    .add_file("de-DE.egl", "items = {count, plural, one {# item}")
+    .add_file("en-US.egl", "title = Title");
+  let i18n = I18n::new();
+  let report = i18n.load_from_source_with_report(&source, LoadMode::Strict);
+  assert!(report.loaded.is_empty());
+  assert!(matches!(report.failed.as_slice(), [I18nError::Parse { .. }]));
+  assert_eq!(report.failed[0].path(), Some(Path::new("de-DE.egl")));
+  assert!(i18n.languages().is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_strict_checks_conflicts_with_loaded_resources() {
+  let dir = common::temp_dir("report", "conflict");
+  common::write(&dir, "de-DE", "title = Titel");
+  let en = common::write(&dir, "en-US", "title = Title");
+  let i18n = I18n::new();
+  i18n.add_resource("en-US", "core", "title = Core title").unwrap();
+
+  let report = i18n.load_translations_from_path_with_report(&dir, LoadMode::Strict);
+  assert!(report.loaded.is_empty());
+  assert!(matches!(report.failed.as_slice(), [I18nError::Resource { .. }]));
+  assert_eq!(report.failed[0].path(), Some(en.as_path()));
+  assert_eq!(languages(&i18n), ["en-US"]);
+  assert_eq!(i18n.resources("en-US"), ["core"]);
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_strict_checks_conflicts_between_new_files() {
+  let source = MemorySource::new()
+    .add_file("a/en-US.ftl", "title = One")
+    .add_file("b/en-US.ftl", "title = Two");
+  let i18n = I18n::new();
+  let report = i18n.load_from_source_with_report(&source, LoadMode::Strict);
+  assert_eq!(report.failed[0].path(), Some(Path::new("b/en-US.ftl")));
+  assert!(i18n.languages().is_empty());
+}
@@ -0,0 +1,181 @@
+//! Integration tests for loading several Fluent resources per language.
+#![cfg(feature = "fluent")]
+
+mod common;
+
+use egui_i18n::fluent::FluentError;
+use egui_i18n::{Args, I18n, I18nError, Override};
+
+fn instance() -> I18n {
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  i18n.set_language("en-US");
+  i18n
+}
+
+fn tr(i18n: &I18n, key: &str) -> String {
+  i18n.translate(key, &Args::new())
+}
+
+// ---------------------------------------------------------------------------
+// add_resource
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_resources_add_up() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Title").unwrap();
+  i18n.add_resource("en-US", "settings", "settings = Settings").unwrap();
+  assert_eq!(tr(&i18n, "title"), "Title");
+  assert_eq!(tr(&i18n, "settings"), "Settings");
+  assert_eq!(i18n.resources("en-US"), ["main", "settings"]);
+  assert_eq!(i18n.languages(), ["en-US"]);
+}
+
+#[test]
+fn test_same_id_replaces_in_place() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Old\nremoved = Gone soon").unwrap();
+  i18n.add_resource("en-US", "settings", "settings = Settings").unwrap();
+  i18n.add_resource("en-US", "main", "title = New").unwrap();
+  assert_eq!(tr(&i18n, "title"), "New");
+  assert_eq!(tr(&i18n, "removed"), "");
+  assert_eq!(i18n.resources("en-US"), ["main", "settings"]);
+}
+
+#[test]
+fn test_text_loads_share_the_unnamed_resource() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "extra", "extra = Extra").unwrap();
+  i18n.load_translations_from_text("en-US", "msg = old").unwrap();
+  i18n.load_translations_from_text("en-US", "msg = new").unwrap();
+  assert_eq!(tr(&i18n, "msg"), "new");
+  assert_eq!(tr(&i18n, "extra"), "Extra");
+  assert_eq!(i18n.resources("en-US"), ["extra", ""]);
+}
+
+#[test]
+fn test_duplicate_across_resources_is_an_error() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Main\n-brand = Acme").unwrap();
+  let err = i18n.add_resource("en-US", "plugin", "title = Plugin\n-brand = Other").unwrap_err();
+  match &err {
+    I18nError::Resource { language, errors, .. } => {
+      assert_eq!(language, "en-US");
+      let ids: Vec<&str> = errors
+        .iter()
+        .map(|e| match e {
+          FluentError::Overriding { id, .. } => id.as_str(),
+          other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+      assert_eq!(ids, ["title", "brand"]);
+    },
+    other => panic!("expected Resource, got {other:?}"),
+  }
+  // Nothing from the rejected resource was loaded.
+  assert_eq!(tr(&i18n, "title"), "Main");
+  assert_eq!(i18n.resources("en-US"), ["main"]);
+}
+
+#[test]
+fn test_failed_reload_keeps_previous_resource() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Main").unwrap();
+  assert!(i18n.add_resource("en-US", "main", "title = {").is_err());
+  assert_eq!(tr(&i18n, "title"), "Main");
+}
+
+// ---------------------------------------------------------------------------
+// add_resource_overriding
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_overriding_resource_wins_and_reports() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Main\nkeep = Kept\n-brand = Acme").unwrap();
+  let overrides =
+    i18n.add_resource_overriding("en-US", "theme", "title = Themed\n-brand = Themed Acme").unwrap();
+  assert_eq!(
+    overrides,
+    [
+      Override { id: "title".into(), resource: "theme".into(), overridden: "main".into() },
+      Override { id: "-brand".into(), resource: "theme".into(), overridden: "main".into() },
+    ]
+  );
+  assert_eq!(tr(&i18n, "title"), "Themed");
+  assert_eq!(tr(&i18n, "-brand"), "Themed Acme");
+  assert_eq!(tr(&i18n, "keep"), "Kept");
+}
+
+#[test]
+fn test_overriding_without_conflicts_reports_nothing() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Main").unwrap();
+  assert!(i18n.add_resource_overriding("en-US", "extra", "other = Other").unwrap().is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// remove_resource
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_remove_resource() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "main", "title = Main").unwrap();
+  i18n.add_resource_overriding("en-US", "theme", "title = Themed").unwrap();
+  assert_eq!(tr(&i18n, "title"), "Themed");
+
+  assert!(i18n.remove_resource("en-US", "theme"));
+  assert_eq!(tr(&i18n, "title"), "Main");
+  assert!(!i18n.remove_resource("en-US", "theme"));
+  assert!(!i18n.remove_resource("de-DE", "main"));
+
+  assert!(i18n.remove_resource("en-US", "main"));
+  assert!(i18n.languages().is_empty());
+  assert!(i18n.resources("en-US").is_empty());
+}
+
+#[test]
+fn test_removing_frees_ids_for_plain_resources() {
+  let i18n = instance();
+  i18n.add_resource("en-US", "a", "title = A").unwrap();
+  assert!(i18n.add_resource("en-US", "b", "title = B").is_err());
+  i18n.remove_resource("en-US", "a");
+  i18n.add_resource("en-US", "b", "title = B").unwrap();
+  assert_eq!(tr(&i18n, "title"), "B");
+}
+
+// ---------------------------------------------------------------------------
+// Paths
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_files_for_the_same_language_add_up() {
+  let main = common::temp_dir("resources", "main");
+  let plugin = common::temp_dir("resources", "plugin");
+  std::fs::write(main.join("en-US.ftl"), "title = Title").unwrap();
+  std::fs::write(plugin.join("en-US.ftl"), "plugin-name = Plugin").unwrap();
+
+  let i18n = instance();
+  i18n.load_translations_from_path(main.to_string_lossy()).unwrap();
+  i18n.load_translations_from_path(plugin.to_string_lossy()).unwrap();
+  assert_eq!(tr(&i18n, "title"), "Title");
+  assert_eq!(tr(&i18n, "plugin-name"), "Plugin");
+
+  // Reloading a file replaces only what it defined.
+  std::fs::write(main.join("en-US.ftl"), "title = Renamed").unwrap();
+  i18n.load_translations_from_path(main.to_string_lossy()).unwrap();
+  assert_eq!(tr(&i18n, "title"), "Renamed");
+  assert_eq!(tr(&i18n, "plugin-name"), "Plugin");
+  assert_eq!(
+    i18n.resources("en-US"),
+    [
+      main.join("en-US.ftl").to_string_lossy().to_string(),
+      plugin.join("en-US.ftl").to_string_lossy().to_string()
+    ]
+  );
+
+  std::fs::remove_dir_all(&main).unwrap();
+  std::fs::remove_dir_all(&plugin).unwrap();
+}
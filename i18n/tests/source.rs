@@ -0,0 +1,172 @@
+//! Integration tests for loading translations through `TranslationSource`.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use egui_i18n::{
+  Args, Embedded, EmbeddedFile, FileSource, Format, I18n, I18nError, MemorySource, SourceEntry,
+  TranslationSource,
+};
+
+fn format() -> Format {
+  Format::default()
+}
+
+fn title(i18n: &I18n, language: &str) -> String {
+  i18n.set_language(language);
+  i18n.translate("title", &Args::new())
+}
+
+// ---------------------------------------------------------------------------
+// Entries
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_entry_from_path() {
+  let entry = SourceEntry::from_path("locales/de-DE.egl", b"title = Titel".as_slice()).unwrap();
+  assert_eq!(entry.id, "locales/de-DE.egl");
+  assert_eq!(entry.language, "de-DE");
+  assert_eq!(entry.format, Format::Classic);
+  assert_eq!(entry.text().unwrap(), "title = Titel");
+
+  let entry = SourceEntry::from_path("en-US.FTL", Vec::new()).unwrap();
+  assert_eq!((entry.language.as_ref(), entry.format), ("en-US", Format::Fluent));
+  assert!(SourceEntry::from_path("README.md", Vec::new()).is_none());
+  assert!(SourceEntry::from_path("en-US", Vec::new()).is_none());
+}
+
+#[test]
+fn test_invalid_utf8_is_invalid_data() {
+  let source = MemorySource::new().add("en-US", format(), [0xff, 0xfe]);
+  match I18n::new().load_from_source(&source).unwrap_err() {
+    I18nError::Io { path, source } => {
+      assert_eq!(path, Path::new(&format!("en-US.{}", format().extension())));
+      assert_eq!(source.kind(), std::io::ErrorKind::InvalidData);
+    },
+    other => panic!("expected Io, got {other:?}"),
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Built-in sources
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_memory_source() {
+  let ext = format().extension();
+  let source = MemorySource::new()
+    .add("en-US", format(), "title = Title")
+    .add_file(format!("bundle/de-DE.{}", ext), "title = Titel")
+    .add_file("bundle/notes.txt", "title = Ignored");
+  let ids: Vec<String> =
+    source.entries().unwrap().into_iter().map(|entry| entry.id.into_owned()).collect();
+  assert_eq!(ids, [format!("en-US.{}", ext), format!("bundle/de-DE.{}", ext)]);
+
+  let i18n = I18n::new();
+  i18n.load_from_source(&source).unwrap();
+  assert_eq!(title(&i18n, "en-US"), "Title");
+  assert_eq!(title(&i18n, "de-DE"), "Titel");
+}
+
+#[test]
+fn test_file_source_matches_load_from_path() {
+  let dir = std::env::temp_dir().join(format!("egui-i18n-source-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join(format!("en-US.{}", format().extension())), "title = Title").unwrap();
+  std::fs::write(dir.join("notes.txt"), "not a catalog").unwrap();
+
+  let source = FileSource::new(&dir);
+  assert_eq!(source.path(), dir);
+  assert_eq!(source.entries().unwrap().len(), 1);
+  let i18n = I18n::new();
+  i18n.load_from_source(&source).unwrap();
+  assert_eq!(title(&i18n, "en-US"), "Title");
+
+  let missing = FileSource::new(dir.join("missing"));
+  assert!(matches!(I18n::new().load_from_source(&missing), Err(I18nError::Io { .. })));
+}
+
+#[test]
+fn test_embedded_source() {
+  static TRANSLATIONS: Embedded = Embedded::new(&[EmbeddedFile {
+    path: "i18n/en-US",
+    language: "en-US",
+    format: Format::Classic,
+    content: "title = Title",
+  }]);
+  let entries = TRANSLATIONS.entries().unwrap();
+  assert_eq!(entries[0].id, "i18n/en-US");
+  assert_eq!(entries[0].bytes.as_ref(), b"title = Title");
+  let i18n = I18n::new();
+  i18n.load_from_source(&TRANSLATIONS).unwrap();
+  assert_eq!(title(&i18n, "en-US"), "Title");
+}
+
+// ---------------------------------------------------------------------------
+// Custom sources
+// ---------------------------------------------------------------------------
+
+/// Files packed as `name\0content\0…`, standing in for an archive format.
+struct Packed(&'static [u8]);
+
+impl TranslationSource for Packed {
+  fn entries(&self) -> Result<Vec<SourceEntry<'_>>, I18nError> {
+    let mut parts = self.0.split(|byte| *byte == 0);
+    let mut entries = vec![];
+    while let (Some(name), Some(content)) = (parts.next(), parts.next()) {
+      let name = std::str::from_utf8(name).map_err(|error| I18nError::Io {
+        path: "packed".into(),
+        source: std::io::Error::new(std::io::ErrorKind::InvalidData, error),
+      })?;
+      entries.extend(SourceEntry::from_path(name, Cow::Borrowed(content)));
+    }
+    Ok(entries)
+  }
+}
+
+#[test]
+fn test_custom_source() {
+  let packed: &'static [u8] = if cfg!(feature = "fluent") {
+    b"en-US.ftl\0title = Title\0de-DE.ftl\0title = Titel\0"
+  } else {
+    b"en-US.egl\0title = Title\0de-DE.egl\0title = Titel\0"
+  };
+  let i18n = I18n::new();
+  i18n.load_from_source(&Packed(packed)).unwrap();
+  assert_eq!(title(&i18n, "en-US"), "Title");
+  assert_eq!(title(&i18n, "de-DE"), "Titel");
+}
+
+#[test]
+fn test_global_load_from_source() {
+  // A language tag no other test loads.
+  let source = MemorySource::new().add("tlh-Latn", format(), "title = Global");
+  egui_i18n::load_from_source(&source).unwrap();
+  assert!(egui_i18n::languages().contains(&"tlh-Latn".to_string()));
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_entries_are_fluent_resources() {
+  let source = MemorySource::new()
+    .add_file("main/en-US.ftl", "title = Title")
+    .add_file("settings/en-US.ftl", "theme = Theme");
+  let i18n = I18n::new();
+  i18n.load_from_source(&source).unwrap();
+  assert_eq!(i18n.resources("en-US"), ["main/en-US.ftl", "settings/en-US.ftl"]);
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_error_names_the_entry() {
+  let source = MemorySource::new()
+    .add_file("a/en-US.ftl", "title = Title")
+    .add_file("b/en-US.ftl", "title = Again");
+  let error = I18n::new().load_from_source(&source).unwrap_err();
+  assert!(matches!(error, I18nError::Resource { .. }));
+  assert_eq!(error.path(), Some(Path::new("b/en-US.ftl")));
+}
@@ -0,0 +1,132 @@
+//! Integration tests for preferred-language detection and negotiation.
+//!
+//! Environment values are injected through `EnvLanguages::with_lookup`, so no
+//! test touches the real process environment, and every test works on its own
+//! `I18n::new()` value.
+
+use std::collections::HashMap;
+
+use egui_i18n::locale::negotiate;
+use egui_i18n::{EnvLanguages, I18n, LanguageSource, StaticLanguages};
+
+fn env(vars: &[(&str, &str)]) -> EnvLanguages {
+  let vars: HashMap<String, String> =
+    vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+  EnvLanguages::with_lookup(move |name| vars.get(name).cloned())
+}
+
+fn instance(languages: &[&str]) -> I18n {
+  let i18n = I18n::new();
+  for language in languages {
+    i18n.load_translations_from_text(language, "k = v").unwrap();
+  }
+  i18n
+}
+
+// ---------------------------------------------------------------------------
+// EnvLanguages
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_env_lang_only() {
+  assert_eq!(env(&[("LANG", "de_DE.UTF-8")]).preferred_languages(), ["de-DE"]);
+}
+
+#[test]
+fn test_env_precedence_lc_all_over_lc_messages_over_lang() {
+  let vars = [("LANG", "de_DE.UTF-8"), ("LC_MESSAGES", "fr_FR.UTF-8"), ("LC_ALL", "ja_JP.UTF-8")];
+  assert_eq!(env(&vars).preferred_languages(), ["ja-JP"]);
+  let vars = [("LANG", "de_DE.UTF-8"), ("LC_MESSAGES", "fr_FR.UTF-8")];
+  assert_eq!(env(&vars).preferred_languages(), ["fr-FR"]);
+}
+
+#[test]
+fn test_env_language_list_comes_first() {
+  let vars = [("LANGUAGE", "zh_TW:zh_CN:en"), ("LANG", "en_US.UTF-8")];
+  assert_eq!(env(&vars).preferred_languages(), ["zh-TW", "zh-CN", "en", "en-US"]);
+}
+
+#[test]
+fn test_env_language_ignored_for_c_locale() {
+  assert!(env(&[("LANGUAGE", "fr"), ("LANG", "C")]).preferred_languages().is_empty());
+  assert!(env(&[("LANGUAGE", "fr")]).preferred_languages().is_empty());
+  assert!(env(&[("LC_ALL", "POSIX")]).preferred_languages().is_empty());
+}
+
+#[test]
+fn test_env_strips_codeset_and_modifier() {
+  assert_eq!(env(&[("LC_ALL", "ca_ES.UTF-8@valencia")]).preferred_languages(), ["ca-ES"]);
+}
+
+#[test]
+fn test_env_empty_values_are_skipped() {
+  assert_eq!(env(&[("LC_ALL", ""), ("LANG", "sv_SE.UTF-8")]).preferred_languages(), ["sv-SE"]);
+}
+
+#[test]
+fn test_static_and_closure_sources() {
+  assert_eq!(StaticLanguages::new(["en-US", "fr"]).preferred_languages(), ["en-US", "fr"]);
+  let source = || vec!["ko-KR".to_string()];
+  assert_eq!(source.preferred_languages(), ["ko-KR"]);
+}
+
+// ---------------------------------------------------------------------------
+// negotiate
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_negotiate_exact_and_normalized() {
+  assert_eq!(negotiate(&["en-US"], &["en_US", "fr"]), ["en_US"]);
+}
+
+#[test]
+fn test_negotiate_more_specific_and_less_specific() {
+  assert_eq!(negotiate(&["en"], &["en-GB", "en-US"]), ["en-GB", "en-US"]);
+  assert_eq!(negotiate(&["zh-Hant-TW"], &["zh", "zh-Hant"]), ["zh-Hant", "zh"]);
+}
+
+#[test]
+fn test_negotiate_same_base_language() {
+  assert_eq!(negotiate(&["pt-PT"], &["pt-BR", "es"]), ["pt-BR"]);
+}
+
+#[test]
+fn test_negotiate_keeps_request_order() {
+  assert_eq!(negotiate(&["ja", "de", "en"], &["en", "de", "fr"]), ["de", "en"]);
+}
+
+#[test]
+fn test_negotiate_no_match() {
+  assert!(negotiate(&["ko"], &["en", "de"]).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// I18n::negotiate_language
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_negotiate_language_sets_language_and_fallbacks() {
+  let i18n = instance(&["en-US", "de-DE", "fr-FR"]);
+  let vars = [("LANGUAGE", "de_AT:fr"), ("LANG", "de_AT.UTF-8")];
+  let selected = i18n.negotiate_language(&env(&vars), "en-US");
+  assert_eq!(selected, "de-DE");
+  assert_eq!(i18n.get_language(), "de-DE");
+  assert_eq!(i18n.get_fallbacks(), ["fr-FR", "en-US"]);
+}
+
+#[test]
+fn test_negotiate_language_uses_default_when_nothing_matches() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  let selected = i18n.negotiate_language(&env(&[("LANG", "ko_KR.UTF-8")]), "en-US");
+  assert_eq!(selected, "en-US");
+  assert_eq!(i18n.get_language(), "en-US");
+  assert!(i18n.get_fallbacks().is_empty());
+}
+
+#[test]
+fn test_negotiate_language_default_not_duplicated() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  i18n.negotiate_language(&StaticLanguages::new(["en-US", "de"]), "en-US");
+  assert_eq!(i18n.get_language(), "en-US");
+  assert_eq!(i18n.get_fallbacks(), ["de-DE"]);
+}
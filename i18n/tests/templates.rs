@@ -0,0 +1,69 @@
+//! Integration tests for pre-parsed classic templates.
+#![cfg(feature = "classic")]
+
+mod common;
+
+use egui_i18n::{placeholder_names, Args, Format};
+
+// ---------------------------------------------------------------------------
+// Substitution
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_inserted_values_are_not_reinterpreted() {
+  let i18n = common::instance(
+    Format::Classic,
+    "pair = {a} and {b}\nitems = {n, plural, one {{name}: # item} other {{name}: # items}}",
+  );
+  let mut args = Args::new();
+  args.set("a", "{b}");
+  args.set("b", "{a}");
+  assert_eq!(i18n.translate("pair", &args), "{b} and {a}");
+
+  let mut args = Args::new();
+  args.set("a", "\\{x\\} {n, plural, other {#}}");
+  args.set("b", "");
+  assert_eq!(i18n.translate("pair", &args), "\\{x\\} {n, plural, other {#}} and ");
+
+  let mut args = Args::new();
+  args.set("n", 2);
+  args.set("name", "#{n}");
+  assert_eq!(i18n.translate("items", &args), "#{n}: 2 items");
+}
+
+#[test]
+fn test_repeated_placeholders() {
+  let i18n = common::instance(Format::Classic, "echo = {word}, {word}, {word}!");
+  let mut args = Args::new();
+  args.set("word", "hey");
+  assert_eq!(i18n.translate("echo", &args), "hey, hey, hey!");
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_reloading_replaces_parsed_templates() {
+  let i18n = common::instance(Format::Classic, "greet = Hello, {name}!");
+  i18n
+    .load_translations_from_text_with_format("en-US", "greet = Hi {name}", Format::Classic)
+    .unwrap();
+  let mut args = Args::new();
+  args.set("name", "Ada");
+  assert_eq!(i18n.translate("greet", &args), "Hi Ada");
+}
+
+// ---------------------------------------------------------------------------
+// placeholder_names
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_placeholder_names() {
+  assert_eq!(placeholder_names("Hello, {name}! \\{escaped\\} {name:number}"), ["name"]);
+  assert_eq!(
+    placeholder_names("{n, plural, one {{who} has # file} other {{who} has # files in {dir}}}"),
+    ["n", "who", "dir"]
+  );
+  assert!(placeholder_names("{ not a placeholder } #").is_empty());
+}
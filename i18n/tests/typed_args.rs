@@ -0,0 +1,165 @@
+//! Integration tests for typed arguments and `{name:format}` placeholders in
+//! the classic format.
+#![cfg(feature = "classic")]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use egui_i18n::{tr, ArgValue, Args, Format, I18n, IntoArg};
+
+const EGL: &str = "
+count = {n:number} files
+total = {price:currency:EUR}
+total-code = {price:currency:USD:code}
+share = {ratio:percent}
+padded = {n:02}:{m:02}
+fixed = {x:.2}
+day = {when:date}
+day-long = {when:date:long}
+clock = {when:time}
+clock-seconds = {when:time:medium}
+stamp = {when:datetime}
+plain = {n} / {when}
+items = {n, plural, one {# item} other {# items}}
+bad = {n:bogus} {n:currency} {:number}
+";
+
+// 2025-01-31T15:04:05Z
+const WHEN: i64 = 1_738_335_845;
+
+fn instance(languages: &[&str]) -> I18n {
+  let i18n = I18n::new();
+  for language in languages {
+    i18n.load_translations_from_text_with_format(language, EGL, Format::Classic).unwrap();
+  }
+  i18n.set_language(languages[0]);
+  i18n
+}
+
+fn tr(i18n: &I18n, key: &str, name: &'static str, value: impl IntoArg<'static>) -> String {
+  let mut args = Args::new();
+  args.set(name, value);
+  i18n.translate(key, &args)
+}
+
+// ---------------------------------------------------------------------------
+// ArgValue
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_conversions_keep_the_type() {
+  assert_eq!(ArgValue::from(3u8), ArgValue::Int(3));
+  assert_eq!(ArgValue::from(&-7i64), ArgValue::Int(-7));
+  assert_eq!(ArgValue::from(u64::MAX), ArgValue::Float(u64::MAX as f64));
+  assert_eq!(ArgValue::from(1.5f32), ArgValue::Float(1.5));
+  assert_eq!(ArgValue::from("hi"), ArgValue::String("hi".into()));
+  assert_eq!(ArgValue::from(&String::from("hi")), ArgValue::String("hi".into()));
+  assert_eq!(ArgValue::from(true), ArgValue::String("true".into()));
+  assert_eq!(ArgValue::from(UNIX_EPOCH + Duration::from_secs(WHEN as u64)), ArgValue::Date(WHEN));
+  assert_eq!(ArgValue::from(UNIX_EPOCH - Duration::from_millis(1500)), ArgValue::Date(-2));
+  assert_eq!(ArgValue::Date(WHEN).to_string(), "2025-01-31T15:04:05Z");
+}
+
+// ---------------------------------------------------------------------------
+// Numbers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_number_formats_follow_the_language() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  assert_eq!(tr(&i18n, "count", "n", 1234567), "1,234,567 files");
+  assert_eq!(tr(&i18n, "total", "price", 1234.5), "€1,234.50");
+  assert_eq!(tr(&i18n, "total-code", "price", 9.99), "USD\u{a0}9.99");
+  assert_eq!(tr(&i18n, "share", "ratio", 0.125), "13%");
+  i18n.set_language("de-DE");
+  assert_eq!(tr(&i18n, "count", "n", 1234567), "1.234.567 files");
+  assert_eq!(tr(&i18n, "total", "price", 1234.5), "1.234,50\u{a0}€");
+  assert_eq!(tr(&i18n, "share", "ratio", 0.125), "13\u{a0}%");
+}
+
+#[test]
+fn test_padding_and_fixed_decimals() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  let mut args = Args::new();
+  args.set("n", 7);
+  args.set("m", 5);
+  assert_eq!(i18n.translate("padded", &args), "07:05");
+  assert_eq!(tr(&i18n, "fixed", "x", 1.23456), "1.23");
+  assert_eq!(tr(&i18n, "fixed", "x", 12345), "12345.00");
+  i18n.set_language("de-DE");
+  assert_eq!(tr(&i18n, "fixed", "x", 1.23456), "1,23");
+}
+
+#[test]
+fn test_strings_that_look_like_numbers_are_formatted() {
+  let i18n = instance(&["en-US"]);
+  assert_eq!(tr(&i18n, "count", "n", "1234"), "1,234 files");
+  assert_eq!(tr(&i18n, "count", "n", "many"), "many files");
+}
+
+// ---------------------------------------------------------------------------
+// Dates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_date_formats_follow_the_language() {
+  let i18n = instance(&["en-US", "de-DE"]);
+  let when = ArgValue::Date(WHEN);
+  assert_eq!(tr(&i18n, "day", "when", when.clone()), "1/31/2025");
+  assert_eq!(tr(&i18n, "day-long", "when", when.clone()), "January 31, 2025");
+  assert_eq!(tr(&i18n, "clock", "when", when.clone()), "3:04\u{a0}PM");
+  assert_eq!(tr(&i18n, "stamp", "when", when.clone()), "1/31/2025, 3:04\u{a0}PM");
+  i18n.set_language("de-DE");
+  assert_eq!(tr(&i18n, "day", "when", when.clone()), "31.01.2025");
+  assert_eq!(tr(&i18n, "day-long", "when", when.clone()), "31. Januar 2025");
+  assert_eq!(tr(&i18n, "clock-seconds", "when", when), "15:04:05");
+}
+
+#[test]
+fn test_dates_from_numbers_and_strings() {
+  let i18n = instance(&["en-GB"]);
+  assert_eq!(tr(&i18n, "day", "when", WHEN), "31/01/2025");
+  assert_eq!(tr(&i18n, "day", "when", "2024-02-29"), "29/02/2024");
+  assert_eq!(tr(&i18n, "day", "when", "soon"), "soon");
+}
+
+// ---------------------------------------------------------------------------
+// Plain placeholders
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_plain_placeholders() {
+  let i18n = instance(&["en-GB"]);
+  let mut args = Args::new();
+  args.set("n", 1234);
+  args.set("when", ArgValue::Date(WHEN));
+  // Numbers as written; dates as the language's short date.
+  assert_eq!(i18n.translate("plain", &args), "1234 / 31/01/2025");
+}
+
+#[test]
+fn test_plural_value_is_a_formatted_number() {
+  let i18n = instance(&["en-US"]);
+  assert_eq!(tr(&i18n, "items", "n", 1), "1 item");
+  assert_eq!(tr(&i18n, "items", "n", 1500), "1,500 items");
+  // Strings are written as given.
+  assert_eq!(tr(&i18n, "items", "n", "1500"), "1500 items");
+}
+
+#[test]
+fn test_unknown_formats_are_kept_as_written() {
+  let i18n = instance(&["en-US"]);
+  assert_eq!(tr(&i18n, "bad", "n", 1), "{n:bogus} {n:currency} {:number}");
+  assert_eq!(i18n.translate("count", &Args::new()), "{n:number} files");
+}
+
+// ---------------------------------------------------------------------------
+// Macros on the default instance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_tr_keeps_argument_types() {
+  egui_i18n::load_translations_from_text_with_format("en-IE", EGL, Format::Classic).unwrap();
+  egui_i18n::set_language("en-IE");
+  assert_eq!(tr!("total", { price: 20 }), "€20.00");
+  assert_eq!(tr!("padded", { n: 9, m: 30 }), "09:30");
+}
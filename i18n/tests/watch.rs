@@ -0,0 +1,236 @@
+//! Integration tests for reloading translation files that change on disk.
+#![cfg(feature = "hot-reload")]
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use egui_i18n::{Args, FileSource, I18n, I18nError, Layout, Watcher};
+
+fn watch(name: &str) -> (PathBuf, I18n, Watcher) {
+  let dir = common::temp_dir("watch", name);
+  common::write(&dir, "en-US", "title = Title");
+  let i18n = I18n::new();
+  i18n.set_language("en-US");
+  let watcher = i18n.watch_path(&dir).unwrap();
+  (dir, i18n, watcher)
+}
+
+fn title(i18n: &I18n) -> String {
+  i18n.translate("title", &Args::new())
+}
+
+// ---------------------------------------------------------------------------
+// Polling
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_watch_loads_the_directory() {
+  let (dir, i18n, watcher) = watch("load");
+  assert_eq!(title(&i18n), "Title");
+  assert_eq!(watcher.path(), dir);
+  assert!(watcher.poll().is_empty());
+}
+
+#[test]
+fn test_missing_directory_is_an_error() {
+  let missing = std::env::temp_dir().join("egui-i18n-watch-does-not-exist");
+  assert!(matches!(I18n::new().watch_path(missing), Err(I18nError::Io { .. })));
+}
+
+#[test]
+fn test_changed_file_is_reloaded() {
+  let (dir, i18n, watcher) = watch("change");
+  let file = common::write(&dir, "en-US", "title = New title");
+  let reload = watcher.poll();
+  assert_eq!(reload.loaded, [file]);
+  assert!(reload.errors.is_empty());
+  assert_eq!(title(&i18n), "New title");
+  assert!(watcher.poll().is_empty());
+}
+
+#[test]
+fn test_added_file_is_loaded() {
+  let (dir, i18n, watcher) = watch("add");
+  let file = common::write(&dir, "de-DE", "title = Titel");
+  assert_eq!(watcher.poll().loaded, [file]);
+  i18n.set_language("de-DE");
+  assert_eq!(title(&i18n), "Titel");
+}
+
+#[test]
+fn test_removed_file_is_unloaded() {
+  let (dir, i18n, watcher) = watch("remove");
+  let file = dir.join(common::file_name("en-US"));
+  fs::remove_file(&file).unwrap();
+  assert_eq!(watcher.poll().removed, [file]);
+  assert!(i18n.languages().is_empty());
+}
+
+#[test]
+fn test_watch_language_dirs() {
+  let dir = common::temp_dir("watch", "layout");
+  fs::create_dir_all(dir.join("en-US")).unwrap();
+  let main = dir.join("en-US").join(common::file_name("main"));
+  fs::write(&main, "title = Title").unwrap();
+  let i18n = I18n::new();
+  i18n.set_language("en-US");
+  let source = FileSource::new(&dir).layout(Layout::LanguageDirs);
+  let watcher = Watcher::new(&i18n, source).unwrap();
+  assert_eq!(title(&i18n), "Title");
+
+  let settings = dir.join("en-US").join(common::file_name("settings"));
+  fs::write(&settings, "theme = Theme").unwrap();
+  assert_eq!(watcher.poll().loaded, [settings.as_path()]);
+  assert_eq!(i18n.translate("theme", &Args::new()), "Theme");
+  assert_eq!(title(&i18n), "Title");
+
+  fs::remove_file(&settings).unwrap();
+  assert_eq!(watcher.poll().removed, [settings]);
+  assert_eq!(i18n.translate("theme", &Args::new()), "");
+  assert_eq!(title(&i18n), "Title");
+}
+
+#[test]
+fn test_watch_domain_dirs() {
+  let dir = common::temp_dir("watch", "domains");
+  fs::create_dir_all(dir.join("plugin-x")).unwrap();
+  let plugin = dir.join("plugin-x").join(common::file_name("en-US"));
+  fs::write(&plugin, "title = Plugin").unwrap();
+  let i18n = I18n::new();
+  i18n.set_language("en-US");
+  let source = FileSource::new(&dir).layout(Layout::DomainDirs);
+  let watcher = Watcher::new(&i18n, source).unwrap();
+  assert_eq!(i18n.translate_in("plugin-x", "title", &Args::new()), "Plugin");
+  assert_eq!(title(&i18n), "");
+
+  fs::write(&plugin, "title = Changed").unwrap();
+  assert_eq!(watcher.poll().loaded, [plugin.as_path()]);
+  assert_eq!(i18n.translate_in("plugin-x", "title", &Args::new()), "Changed");
+
+  fs::remove_file(&plugin).unwrap();
+  assert_eq!(watcher.poll().removed, [plugin]);
+  assert!(i18n.domain_languages("plugin-x").is_empty());
+}
+
+#[test]
+fn test_unreadable_file_keeps_previous_catalog() {
+  let (dir, i18n, watcher) = watch("unreadable");
+  let file = common::write(&dir, "en-US", [0xff, 0xfe, b'\n']);
+  let reload = watcher.poll();
+  assert!(reload.loaded.is_empty());
+  assert_eq!(reload.errors.len(), 1);
+  assert_eq!(reload.errors[0].path(), Some(file.as_path()));
+  assert_eq!(title(&i18n), "Title");
+  // Reported once per edit.
+  assert!(watcher.poll().is_empty());
+
+  common::write(&dir, "en-US", "title = Fixed");
+  assert_eq!(watcher.poll().loaded, [file]);
+  assert_eq!(title(&i18n), "Fixed");
+}
+
+#[test]
+fn test_initial_failures_are_reported_by_the_first_poll() {
+  let dir = common::temp_dir("watch", "initial");
+  common::write(&dir, "en-US", "title = Title");
+  let broken = common::write(&dir, "de-DE", [0xff, 0xfe, b'\n']);
+  let i18n = I18n::new();
+  i18n.set_language("en-US");
+  let watcher = i18n.watch_path(&dir).unwrap();
+  assert_eq!(title(&i18n), "Title");
+
+  let reload = watcher.poll();
+  assert!(reload.loaded.is_empty());
+  assert_eq!(reload.errors.len(), 1);
+  assert_eq!(reload.errors[0].path(), Some(broken.as_path()));
+  assert!(watcher.poll().is_empty());
+
+  common::write(&dir, "de-DE", "title = Titel");
+  assert_eq!(watcher.poll().loaded, [broken]);
+}
+
+#[test]
+fn test_unreadable_directory_is_reported_once() {
+  let (dir, i18n, watcher) = watch("gone");
+  fs::remove_dir_all(&dir).unwrap();
+  let reload = watcher.poll();
+  assert!(matches!(reload.errors.as_slice(), [I18nError::Io { .. }]));
+  assert!(watcher.poll().is_empty());
+  assert_eq!(title(&i18n), "Title");
+}
+
+#[test]
+fn test_on_reload_sees_changes_only() {
+  let (dir, _i18n, watcher) = watch("callback");
+  let (sender, receiver) = mpsc::channel();
+  let sender = std::sync::Mutex::new(sender);
+  let watcher = watcher.on_reload(move |reload| {
+    sender.lock().unwrap().send(reload.loaded.clone()).unwrap();
+  });
+  watcher.poll();
+  assert!(receiver.try_recv().is_err());
+
+  let file = common::write(&dir, "en-US", "title = Again");
+  watcher.poll();
+  assert_eq!(receiver.try_recv().unwrap(), [file]);
+}
+
+#[test]
+fn test_spawned_watcher_reloads() {
+  let (dir, i18n, watcher) = watch("spawn");
+  let (sender, receiver) = mpsc::channel();
+  let sender = std::sync::Mutex::new(sender);
+  let watcher = watcher
+    .on_reload(move |_| {
+      let _ = sender.lock().unwrap().send(());
+    })
+    .spawn(Duration::from_millis(10));
+
+  common::write(&dir, "en-US", "title = From the thread");
+  receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+  assert_eq!(title(&i18n), "From the thread");
+
+  // Dropping the watcher stops the thread.
+  drop(watcher);
+  common::write(&dir, "en-US", "title = Not seen");
+  std::thread::sleep(Duration::from_millis(50));
+  assert_eq!(title(&i18n), "From the thread");
+}
+
+// ---------------------------------------------------------------------------
+// Fluent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "fluent")]
+#[test]
+fn test_syntax_error_keeps_previous_catalog() {
+  let (dir, i18n, watcher) = watch("syntax");
+  common::write(&dir, "en-US", "title = Broken\n!!!\n");
+  let reload = watcher.poll();
+  assert!(matches!(reload.errors.as_slice(), [I18nError::Parse { .. }]));
+  assert_eq!(title(&i18n), "Title");
+}
+
+// ---------------------------------------------------------------------------
+// egui
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "egui")]
+#[test]
+fn test_reload_requests_repaint() {
+  let (dir, _i18n, watcher) = watch("repaint");
+  let ctx = egui_i18n::egui::Context::default();
+  let watcher = watcher.request_repaint(&ctx);
+  // A fresh context repaints anyway; let one pass settle it.
+  let _ = ctx.run(Default::default(), |_| {});
+  let _ = ctx.run(Default::default(), |_| {});
+  assert!(!ctx.has_requested_repaint());
+
+  common::write(&dir, "en-US", "title = Repainted");
+  watcher.poll();
+  assert!(ctx.has_requested_repaint());
+}
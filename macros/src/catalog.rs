@@ -0,0 +1,65 @@
+//! The reference language's catalogs, read once per version of the files.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use egui_i18n::codegen::Catalog;
+
+struct Cached {
+  stamps: Vec<(PathBuf, Option<SystemTime>)>,
+  catalog: Arc<Catalog>,
+}
+
+/// The catalog of `reference` in `dir`. A crate expands `tr!` many times, so
+/// it is parsed again only when one of its files changed.
+pub fn load(dir: &Path, reference: &str) -> Result<Arc<Catalog>, String> {
+  static CACHE: OnceLock<Mutex<HashMap<(PathBuf, String), Cached>>> = OnceLock::new();
+
+  let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+  let key = (dir.to_path_buf(), reference.to_string());
+  if let Some(cached) =
+    cache.get(&key).filter(|cached| cached.stamps == stamps(&cached.catalog.files))
+  {
+    return Ok(cached.catalog.clone());
+  }
+  let catalog = Arc::new(Catalog::read(dir, reference).map_err(|error| error.to_string())?);
+  cache.insert(key, Cached { stamps: stamps(&catalog.files), catalog: catalog.clone() });
+  Ok(catalog)
+}
+
+fn stamps(files: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+  files
+    .iter()
+    .map(|file| (file.clone(), fs::metadata(file).and_then(|m| m.modified()).ok()))
+    .collect()
+}
+
+/// The known key closest to `key`, for a "did you mean" hint.
+pub fn suggest<'a>(catalog: &'a Catalog, key: &str) -> Option<&'a str> {
+  catalog
+    .messages
+    .keys()
+    .map(|candidate| (distance(key, candidate), candidate))
+    .filter(|(distance, _)| *distance <= (key.chars().count() / 3).max(1))
+    .min()
+    .map(|(_, candidate)| candidate.as_str())
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, ca) in a.chars().enumerate() {
+    let mut previous = row[0];
+    row[0] = i + 1;
+    for (j, cb) in b.iter().enumerate() {
+      let current = row[j + 1];
+      row[j + 1] = if ca == *cb { previous } else { 1 + previous.min(row[j]).min(current) };
+      previous = current;
+    }
+  }
+  row[b.len()]
+}
@@ -0,0 +1,121 @@
+//! `include_translations!`: a directory of catalogs, checked and embedded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+use egui_i18n::unic_langid::LanguageIdentifier;
+use egui_i18n::{check_translations, line_column, Format};
+
+struct File {
+  /// Absolute, for `include_str!`.
+  full: PathBuf,
+  /// As written in the macro call, joined with the file name.
+  path: String,
+  language: String,
+  format: Format,
+}
+
+pub fn expand(dir: &LitStr) -> syn::Result<TokenStream> {
+  let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+  let full_dir = Path::new(&manifest_dir).join(dir.value());
+  let files = files(dir, &full_dir)?;
+
+  let mut errors: Vec<syn::Error> = vec![];
+  for file in &files {
+    if let Err(message) = check(file) {
+      errors.push(syn::Error::new(dir.span(), message));
+    }
+  }
+  let mut errors = errors.into_iter();
+  if let Some(mut error) = errors.next() {
+    error.extend(errors);
+    return Err(error);
+  }
+
+  let entries = files.iter().map(|file| {
+    let full = file.full.to_string_lossy().to_string();
+    let path = &file.path;
+    let language = &file.language;
+    let format = match file.format {
+      Format::Classic => quote!(::egui_i18n::Format::Classic),
+      Format::Fluent => quote!(::egui_i18n::Format::Fluent),
+    };
+    quote! {
+      ::egui_i18n::EmbeddedFile {
+        path: #path,
+        language: #language,
+        format: #format,
+        content: ::core::include_str!(#full),
+      }
+    }
+  });
+  Ok(quote! {
+    ::egui_i18n::Embedded::new(&[#(#entries),*])
+  })
+}
+
+/// The `.egl` and `.ftl` files directly in `full_dir`, sorted, each named
+/// after its stem as `load_translations_from_path` does.
+fn files(dir: &LitStr, full_dir: &Path) -> syn::Result<Vec<File>> {
+  let read_dir = fs::read_dir(full_dir).map_err(|error| {
+    let message = format!("cannot read `{}`: {}", full_dir.display(), error);
+    syn::Error::new(dir.span(), message)
+  })?;
+  let mut files: Vec<File> = read_dir
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|full| full.is_file())
+    .filter_map(|full| {
+      let format = Format::from_extension(full.extension()?.to_string_lossy())?;
+      let language = full.file_stem()?.to_string_lossy().to_string();
+      let name = full.file_name()?.to_string_lossy().to_string();
+      let path = format!("{}/{}", dir.value().trim_end_matches(['/', '\\']), name);
+      Some(File { full, path, language, format })
+    })
+    .collect();
+  if files.is_empty() {
+    let message = format!("no `.egl` or `.ftl` files in `{}`", full_dir.display());
+    return Err(syn::Error::new(dir.span(), message));
+  }
+  files.sort_by(|a, b| a.path.cmp(&b.path));
+  Ok(files)
+}
+
+/// What would fail when the file is loaded.
+fn check(file: &File) -> Result<(), String> {
+  let content = fs::read_to_string(&file.full)
+    .map_err(|error| format!("cannot read `{}`: {}", file.path, error))?;
+  if file.format != Format::Fluent {
+    let errors = check_translations(&content);
+    if errors.is_empty() {
+      return Ok(());
+    }
+    let errors: Vec<String> = errors
+      .iter()
+      .map(|error| format!("{}:{}:{}: {}", file.path, error.line, error.column, error.error))
+      .collect();
+    return Err(format!("failed to parse {}", errors.join("; ")));
+  }
+  if file.language.parse::<LanguageIdentifier>().is_err() {
+    return Err(format!(
+      "`{}` is not a valid language identifier, which Fluent files need; rename `{}`",
+      file.language, file.path
+    ));
+  }
+  match fluent_syntax::parser::parse_runtime(content.as_str()) {
+    Ok(_) => Ok(()),
+    Err((_, errors)) => {
+      let errors: Vec<String> = errors
+        .iter()
+        .map(|error| {
+          let (line, column) = line_column(&content, error.pos.start);
+          format!("{}:{}:{}: {}", file.path, line, column, error)
+        })
+        .collect();
+      Err(format!("failed to parse {}", errors.join("; ")))
+    },
+  }
+}
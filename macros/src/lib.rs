@@ -0,0 +1,310 @@
+//! A `tr!` that checks its key and arguments against the catalogs at compile
+//! time.
+//!
+//! It expands to the same call as `egui_i18n::tr!`, but the key must be a
+//! string literal that the reference language defines, and the named
+//! arguments must be exactly the placeholders of that message: `{name}` in
+//! `.egl`, `$name` in `.ftl`. Mistakes are compile errors pointing at the key
+//! or the argument.
+//!
+//! The catalogs are read from the directory in `EGUI_I18N_DIR`, relative to
+//! the crate's manifest (default `i18n`), and the reference language is
+//! `EGUI_I18N_REFERENCE` (default `en-US`). Both can be set for a whole
+//! workspace in `.cargo/config.toml`, where `relative` makes the directory
+//! relative to the config file instead:
+//!
+//! ```toml
+//! [env]
+//! EGUI_I18N_DIR = { value = "assets/languages", relative = true }
+//! EGUI_I18N_REFERENCE = "en_US"
+//! ```
+//!
+//! Editing a reference catalog recompiles the crates that use the macro.
+//!
+//! `tr!(domain = "plugin-x", "save")` looks the key up in the catalogs of
+//! `<EGUI_I18N_DIR>/plugin-x` first and then, as the runtime falls back to
+//! the core domain, in those of the directory itself.
+//!
+//! `tr!(ctx: "menu", "Open")` checks the message written `[menu] Open = …`,
+//! or `Open` itself, which the runtime falls back to.
+//!
+//! [`include_translations!`] embeds a whole directory of catalogs, checked
+//! the same way they are checked when loaded.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, Expr, Ident, LitStr, Token};
+
+use egui_i18n::codegen::Catalog;
+
+mod catalog;
+mod embed;
+
+const DEFAULT_DIR: &str = "i18n";
+const DEFAULT_REFERENCE: &str = "en-US";
+
+/// Translate a key checked against the reference catalog.
+///
+/// ```ignore
+/// use egui_i18n_macros::tr;
+///
+/// ui.label(tr!("welcome"));
+/// ui.label(tr!("hello-name", { name: &self.name }));
+/// ui.label(tr!(domain = "plugin-x", "save"));
+/// ui.label(tr!(ctx: "menu", "Open"));
+/// ```
+#[proc_macro]
+pub fn tr(input: TokenStream) -> TokenStream {
+  let input = syn::parse_macro_input!(input as Input);
+  match expand(input) {
+    Ok(tokens) => tokens.into(),
+    Err(error) => compile_errors(error).into(),
+  }
+}
+
+/// Embed every `.egl` and `.ftl` file in a directory, relative to the
+/// crate's manifest, as an `egui_i18n::Embedded` to load with
+/// `egui_i18n::load_embedded`. Files are named after their stem, as
+/// `load_translations_from_path` names them.
+///
+/// Fluent files are parsed while compiling, so a syntax error or a file stem
+/// that is not a language identifier is a compile error instead of a load
+/// error. Editing a file recompiles the crate; adding or removing one needs
+/// a rebuild, e.g. after `touch`ing the file with the macro call.
+///
+/// ```ignore
+/// static TRANSLATIONS: egui_i18n::Embedded =
+///   egui_i18n_macros::include_translations!("assets/languages");
+///
+/// egui_i18n::load_embedded(&TRANSLATIONS)?;
+/// ```
+#[proc_macro]
+pub fn include_translations(input: TokenStream) -> TokenStream {
+  let dir = syn::parse_macro_input!(input as LitStr);
+  match embed::expand(&dir) {
+    Ok(tokens) => tokens.into(),
+    Err(error) => compile_errors(error).into(),
+  }
+}
+
+/// Every error of `error` as an expression, so several are reported where
+/// one value is expected too.
+fn compile_errors(error: syn::Error) -> proc_macro2::TokenStream {
+  let errors = error.to_compile_error();
+  quote!({ #errors })
+}
+
+/// `"key"` or `"key", { name: value, … }`, optionally after
+/// `domain = "name",` and `ctx: "context",`.
+struct Input {
+  domain: Option<LitStr>,
+  context: Option<LitStr>,
+  key: LitStr,
+  args: Vec<Arg>,
+}
+
+impl Input {
+  /// The key in the catalogs, a context key with a context.
+  fn catalog_key(&self) -> String {
+    match &self.context {
+      Some(context) => egui_i18n::context_key(&context.value(), &self.key.value()),
+      None => self.key.value(),
+    }
+  }
+}
+
+struct Arg {
+  name: Ident,
+  value: Expr,
+}
+
+impl Parse for Input {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let mut domain = None;
+    if input.peek(Ident) && input.peek2(Token![=]) {
+      let name: Ident = input.parse()?;
+      if name != "domain" {
+        return Err(syn::Error::new(name.span(), "expected `domain = \"…\"`"));
+      }
+      input.parse::<Token![=]>()?;
+      domain = Some(
+        input
+          .parse::<LitStr>()
+          .map_err(|error| syn::Error::new(error.span(), "expected a string literal domain"))?,
+      );
+      input.parse::<Token![,]>()?;
+    }
+    let mut context = None;
+    if input.peek(Ident) && input.peek2(Token![:]) {
+      let name: Ident = input.parse()?;
+      if name != "ctx" {
+        return Err(syn::Error::new(name.span(), "expected `ctx: \"…\"`"));
+      }
+      input.parse::<Token![:]>()?;
+      context = Some(
+        input
+          .parse::<LitStr>()
+          .map_err(|error| syn::Error::new(error.span(), "expected a string literal context"))?,
+      );
+      input.parse::<Token![,]>()?;
+    }
+    let key: LitStr = input.parse().map_err(|error| {
+      let message =
+        "expected a string literal key; use `egui_i18n::tr!` for keys computed at runtime";
+      syn::Error::new(error.span(), message)
+    })?;
+    let mut args = vec![];
+    if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+      let content;
+      braced!(content in input);
+      args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?.into_iter().collect();
+      input.parse::<Option<Token![,]>>()?;
+    }
+    Ok(Self { domain, context, key, args })
+  }
+}
+
+impl Parse for Arg {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let name = input.parse()?;
+    input.parse::<Token![:]>()?;
+    Ok(Self { name, value: input.parse()? })
+  }
+}
+
+fn expand(input: Input) -> syn::Result<proc_macro2::TokenStream> {
+  let config = Config::from_env();
+  // Searched in order: the domain's catalogs, then the core ones.
+  let mut catalogs = vec![];
+  if let Some(domain) = &input.domain {
+    let dir = config.dir.join(domain.value());
+    let catalog = catalog::load(&dir, &config.reference)
+      .map_err(|error| syn::Error::new(domain.span(), format!("tr!: {}", error)))?;
+    catalogs.push((dir, catalog));
+  }
+  match catalog::load(&config.dir, &config.reference) {
+    Ok(catalog) => catalogs.push((config.dir.clone(), catalog)),
+    // A domain's keys do not need core catalogs.
+    Err(_) if input.domain.is_some() => {},
+    Err(error) => return Err(syn::Error::new(Span::call_site(), format!("tr!: {}", error))),
+  }
+  check(&catalogs, &config, &input)?;
+
+  let key = LitStr::new(&input.catalog_key(), input.key.span());
+  let names = input.args.iter().map(|arg| arg.name.to_string());
+  let values = input.args.iter().map(|arg| &arg.value);
+  let files = catalogs
+    .iter()
+    .flat_map(|(_, catalog)| catalog.files.iter())
+    .map(|file| file.to_string_lossy().to_string());
+  let translate = match &input.domain {
+    Some(domain) => quote!(::egui_i18n::translate_in(#domain, #key, &args)),
+    None => quote!(::egui_i18n::translate(#key, &args)),
+  };
+  Ok(quote! {{
+    #(const _: &[u8] = ::core::include_bytes!(#files);)*
+    #[allow(unused_mut)]
+    let mut args = ::egui_i18n::Args::new();
+    #(args.set(#names, #values);)*
+    #translate
+  }})
+}
+
+/// Where the reference catalogs are.
+struct Config {
+  dir: PathBuf,
+  reference: String,
+}
+
+impl Config {
+  fn from_env() -> Self {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let dir = std::env::var("EGUI_I18N_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string());
+    let reference =
+      std::env::var("EGUI_I18N_REFERENCE").unwrap_or_else(|_| DEFAULT_REFERENCE.to_string());
+    Self { dir: PathBuf::from(manifest_dir).join(dir), reference }
+  }
+}
+
+/// Every mistake at once: the key, then each argument that is not a
+/// placeholder, then the placeholders left without an argument. The key is
+/// looked up in `catalogs` in order, and as at runtime, a key with a context
+/// falls back to the key without one.
+fn check(catalogs: &[(PathBuf, Arc<Catalog>)], config: &Config, input: &Input) -> syn::Result<()> {
+  let find = |key: &str| catalogs.iter().find_map(|(_, catalog)| catalog.messages.get(key));
+  let key = display(&input.catalog_key());
+  let found = find(&input.catalog_key()).or_else(|| match &input.context {
+    Some(_) => find(&input.key.value()),
+    None => None,
+  });
+  let Some(message) = found else {
+    let dirs: Vec<String> =
+      catalogs.iter().map(|(dir, _)| format!("`{}`", dir.display())).collect();
+    let mut message = format!(
+      "`{}` is not defined for the reference language `{}` in {}",
+      key,
+      config.reference,
+      dirs.join(" or ")
+    );
+    if let Some(suggestion) =
+      catalogs.iter().find_map(|(_, catalog)| catalog::suggest(catalog, &input.catalog_key()))
+    {
+      message.push_str(&format!("; did you mean `{}`?", display(suggestion)));
+    }
+    return Err(syn::Error::new(input.key.span(), message));
+  };
+  let placeholders = &message.placeholders;
+
+  let mut errors: Vec<syn::Error> = vec![];
+  for (i, arg) in input.args.iter().enumerate() {
+    let name = arg.name.to_string();
+    let message = if input.args[..i].iter().any(|other| other.name == arg.name) {
+      format!("`{}` is given more than once", name)
+    } else if !placeholders.contains(&name) {
+      match placeholders.len() {
+        0 => format!("`{}` takes no arguments", key),
+        _ => format!("`{}` has no placeholder `{}`; it takes {}", key, name, list(placeholders)),
+      }
+    } else {
+      continue;
+    };
+    errors.push(syn::Error::new(arg.name.span(), message));
+  }
+  let missing: Vec<String> = placeholders
+    .iter()
+    .filter(|name| !input.args.iter().any(|arg| arg.name == name.as_str()))
+    .cloned()
+    .collect();
+  if !missing.is_empty() {
+    let message = format!("`{}` needs {}", key, list(&missing));
+    errors.push(syn::Error::new(input.key.span(), message));
+  }
+
+  let mut errors = errors.into_iter();
+  match errors.next() {
+    Some(mut error) => {
+      error.extend(errors);
+      Err(error)
+    },
+    None => Ok(()),
+  }
+}
+
+/// `key` as a `.egl` file writes it, `[context] key` for a context key.
+fn display(key: &str) -> String {
+  match egui_i18n::split_context_key(key) {
+    Some((context, key)) => format!("[{}] {}", context, key),
+    None => key.to_string(),
+  }
+}
+
+fn list(names: &[String]) -> String {
+  let names: Vec<String> = names.iter().map(|name| format!("`{}`", name)).collect();
+  names.join(", ")
+}
@@ -0,0 +1,2 @@
+title = Title
+!!! broken
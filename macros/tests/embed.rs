@@ -0,0 +1,38 @@
+//! `include_translations!` against the catalogs in `tests/i18n`; the
+//! compile errors are checked in `tests/ui/fail_embed.rs`.
+
+use egui_i18n::{Args, Embedded, Format, I18n};
+use egui_i18n_macros::include_translations;
+
+static TRANSLATIONS: Embedded = include_translations!("tests/i18n");
+
+#[test]
+fn test_files_are_embedded_sorted() {
+  let files: Vec<_> =
+    TRANSLATIONS.files().iter().map(|file| (file.path, file.language, file.format)).collect();
+  assert_eq!(
+    files,
+    [
+      ("tests/i18n/de-DE.egl", "de-DE", Format::Classic),
+      ("tests/i18n/en-US.egl", "en-US", Format::Classic),
+      ("tests/i18n/en-US.ftl", "en-US", Format::Fluent),
+    ]
+  );
+  assert_eq!(TRANSLATIONS.languages(), ["de-DE", "en-US"]);
+  assert_eq!(TRANSLATIONS.files()[0].content, include_str!("i18n/de-DE.egl"));
+}
+
+#[test]
+fn test_load_embedded() {
+  let i18n = I18n::new();
+  i18n.set_use_isolating(false);
+  i18n.load_embedded(&TRANSLATIONS).unwrap();
+  i18n.set_language("en-US");
+  assert_eq!(i18n.translate("welcome", &Args::new()), "Welcome");
+  let mut args = Args::new();
+  args.set("user", "Ferris");
+  assert_eq!(i18n.translate("hello-user", &args), "Hi Ferris");
+  i18n.set_language("de-DE");
+  assert_eq!(i18n.translate("welcome", &Args::new()), "Willkommen");
+  assert_eq!(i18n.resources("en-US"), ["tests/i18n/en-US.ftl"]);
+}
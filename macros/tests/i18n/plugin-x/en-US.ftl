@@ -0,0 +1,2 @@
+save = Save as file
+export = Export as { $format }
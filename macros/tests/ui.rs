@@ -0,0 +1,11 @@
+//! Compile-time checks of `tr!`, against the catalogs in `tests/i18n`, and
+//! of `include_translations!`.
+
+#[test]
+fn test_ui() {
+  std::env::set_var("EGUI_I18N_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/i18n"));
+  std::env::set_var("EGUI_I18N_REFERENCE", "en-US");
+  let cases = trybuild::TestCases::new();
+  cases.pass("tests/ui/pass.rs");
+  cases.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,10 @@
+use egui_i18n_macros::tr;
+
+fn main() {
+  tr!("welcome", { name: "Ada" });
+  tr!("hello-name", { nmae: "Ada" });
+  tr!("files", { count: 2, count: 3 });
+  tr!("greeting", { user: "Ada" });
+  tr!("login.tooltip");
+  let _label: String = tr!("hello-name", { nmae: "Ada" });
+}
@@ -0,0 +1,7 @@
+use egui_i18n_macros::tr;
+
+fn main() {
+  tr!(ctx: "menu", "Opne");
+  tr!(ctx: "menu", "Save");
+  tr!(context: "menu", "Open");
+}
@@ -0,0 +1,8 @@
+use egui_i18n_macros::tr;
+
+fn main() {
+  tr!(domain = "plugin-x", "sav");
+  tr!(domain = "plugin-x", "export", { fmt: "PNG" });
+  tr!(domain = "plugin-y", "save");
+  tr!(domian = "plugin-x", "save");
+}
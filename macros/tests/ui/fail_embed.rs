@@ -0,0 +1,10 @@
+use egui_i18n::Embedded;
+use egui_i18n_macros::include_translations;
+
+// trybuild builds this from `target/tests/trybuild/egui-i18n-macros`, so
+// `tests/broken` is reached from the workspace root.
+static MISSING: Embedded = include_translations!("tests/does-not-exist");
+static BROKEN: Embedded = include_translations!("../../../../macros/tests/broken");
+static NOT_A_LITERAL: Embedded = include_translations!(concat!("tests/", "i18n"));
+
+fn main() {}
@@ -0,0 +1,8 @@
+use egui_i18n_macros::tr;
+
+fn main() {
+  tr!("welcom");
+  tr!("only-in-german");
+  let key = "welcome";
+  tr!(key);
+}
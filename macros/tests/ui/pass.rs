@@ -0,0 +1,30 @@
+use egui_i18n_macros::tr;
+
+fn main() {
+  egui_i18n::set_use_isolating(false);
+  egui_i18n::load_translations_from_path(concat!(env!("EGUI_I18N_DIR"))).unwrap();
+  let plugin = egui_i18n::FileSource::new(concat!(env!("EGUI_I18N_DIR"), "/plugin-x"));
+  egui_i18n::load_domain_from_source("plugin-x", &plugin).unwrap();
+  egui_i18n::set_language("en-US");
+
+  let name = String::from("Ada");
+  assert_eq!(tr!("welcome"), "Welcome");
+  assert_eq!(tr!("hello-name", { name: &name }), "Hello, Ada!");
+  assert_eq!(tr!("files", { count: 2, folder: "Inbox" }), "2 files in Inbox");
+  assert_eq!(tr!("inbox", { count: 1 }), "One message");
+  assert_eq!(tr!("greeting", { user: "Ada", place: "egui" }), "Hi Ada, welcome to egui");
+  assert_eq!(tr!("login.tooltip", { user: "Ada" }), "Sign in as Ada");
+  assert_eq!(tr!("-brand"), "Egui");
+
+  assert_eq!(tr!(domain = "plugin-x", "save"), "Save as file");
+  assert_eq!(tr!(domain = "plugin-x", "export", { format: "PNG" }), "Export as PNG");
+  // Not in the domain, so from the core catalogs.
+  assert_eq!(tr!(domain = "plugin-x", "welcome"), "Welcome");
+
+  assert_eq!(tr!(ctx: "menu", "Open"), "Open…");
+  assert_eq!(tr!(ctx: "status", "Open"), "Opened");
+  assert_eq!(tr!(ctx: "menu", "Save", { name: &name }), "Save Ada");
+  // No message for the context, so the one without.
+  assert_eq!(tr!(ctx: "menu", "Close"), "Close");
+  assert_eq!(tr!(domain = "plugin-x", ctx: "menu", "Open"), "Open…");
+}